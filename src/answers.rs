@@ -0,0 +1,190 @@
+// Known-correct answers, for catching regressions.
+//
+// The answers file has one answer per line: the day, the part, a
+// fingerprint of the input and the answer, separated by spaces. Newlines
+// in answers are written as `\n`. Lines starting with `#` are comments.
+//
+use crate::parse::ParseError;
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Name of the answers file in an inputs directory
+pub const FILE_NAME: &str = "answers.txt";
+
+/// FNV-1a hash of the input, ignoring surrounding whitespace
+pub fn fingerprint(input: &str) -> u64 {
+    input.trim().bytes().fold(0xcbf2_9ce4_8422_2325, |h, b| {
+        (h ^ b as u64).wrapping_mul(0x0000_0100_0000_01b3)
+    })
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Verdict {
+    Pass,
+    /// The answer differs from the recorded one
+    Changed {
+        expected: String,
+    },
+    /// The solution returned an error
+    Failed(String),
+    /// Nothing recorded for this input
+    Unknown,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Verdict::Pass => write!(f, "PASS"),
+            Verdict::Changed { .. } => write!(f, "CHANGED"),
+            Verdict::Failed(_) => write!(f, "FAIL"),
+            Verdict::Unknown => write!(f, "UNKNOWN"),
+        }
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Answers {
+    entries: BTreeMap<(u8, u8, u64), String>,
+}
+
+impl Answers {
+    pub fn new() -> Answers {
+        Answers::default()
+    }
+
+    pub fn parse(s: &str) -> Result<Answers, ParseError> {
+        let mut answers = Answers::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let error = |column, token: &str, expected| ParseError {
+                line: i + 1,
+                column,
+                token: token.to_string(),
+                expected,
+            };
+            let mut fields = line.splitn(4, ' ');
+            let mut column = 1;
+            let mut next = |expected| {
+                let field = fields.next().unwrap_or("");
+                let at = column;
+                column += field.chars().count() + 1;
+                if field.is_empty() {
+                    Err(error(at, field, expected))
+                } else {
+                    Ok((at, field))
+                }
+            };
+            let (at, day) = next("a day")?;
+            let day = day.parse().map_err(|_| error(at, day, "a day"))?;
+            let (at, part) = next("a part")?;
+            let part = part.parse().map_err(|_| error(at, part, "a part"))?;
+            let (at, fp) = next("an input fingerprint")?;
+            let fp =
+                u64::from_str_radix(fp, 16).map_err(|_| error(at, fp, "an input fingerprint"))?;
+            let (_, answer) = next("an answer")?;
+            answers.entries.insert((day, part, fp), unescape(answer));
+        }
+        Ok(answers)
+    }
+
+    pub fn get(&self, day: u8, part: u8, input: &str) -> Option<&str> {
+        self.entries
+            .get(&(day, part, fingerprint(input)))
+            .map(String::as_str)
+    }
+
+    pub fn record(&mut self, day: u8, part: u8, input: &str, answer: &str) {
+        self.entries
+            .insert((day, part, fingerprint(input)), answer.to_string());
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Compares the result of a part against the recorded answer
+    pub fn check<E: fmt::Display>(
+        &self,
+        day: u8,
+        part: u8,
+        input: &str,
+        result: &Result<String, E>,
+    ) -> Verdict {
+        match (result, self.get(day, part, input)) {
+            (Err(e), _) => Verdict::Failed(e.to_string()),
+            (Ok(_), None) => Verdict::Unknown,
+            (Ok(answer), Some(expected)) if answer == expected => Verdict::Pass,
+            (Ok(_), Some(expected)) => Verdict::Changed {
+                expected: expected.to_string(),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Answers {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day part input answer")?;
+        for ((day, part, fp), answer) in &self.entries {
+            writeln!(f, "{} {} {:016x} {}", day, part, fp, escape(answer))?;
+        }
+        Ok(())
+    }
+}
+
+fn escape(s: &str) -> String {
+    s.replace('\\', "\\\\").replace('\n', "\\n")
+}
+
+fn unescape(s: &str) -> String {
+    let mut out = String::new();
+    let mut chars = s.chars();
+    while let Some(c) = chars.next() {
+        match (c, chars.clone().next()) {
+            ('\\', Some('n')) => {
+                out.push('\n');
+                chars.next();
+            }
+            ('\\', Some('\\')) => {
+                out.push('\\');
+                chars.next();
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[test]
+fn test_answers() {
+    let mut answers = Answers::new();
+    answers.record(1, 1, "12\n14\n", "4");
+    answers.record(8, 2, "0122", "# #\n \\#");
+    let text = answers.to_string();
+    assert_eq!(Answers::parse(&text), Ok(answers.clone()));
+    assert_eq!(answers.get(1, 1, "12\n14"), Some("4"));
+    assert_eq!(answers.get(1, 1, "12\n15"), None);
+
+    let ok = |s: &str| Ok::<_, String>(s.to_string());
+    assert_eq!(answers.check(1, 1, "12\n14", &ok("4")), Verdict::Pass);
+    assert_eq!(
+        answers.check(1, 1, "12\n14", &ok("5")),
+        Verdict::Changed {
+            expected: "4".to_string()
+        }
+    );
+    assert_eq!(answers.check(1, 2, "12\n14", &ok("5")), Verdict::Unknown);
+    assert_eq!(
+        answers.check(1, 1, "12\n14", &Err("bad input".to_string())),
+        Verdict::Failed("bad input".to_string())
+    );
+
+    let err = Answers::parse("# comment\n1 1 00ff 4\n1 x 00ff 4\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (3, 3, "x"));
+    assert!(Answers::parse("1 1 00ff\n").is_err());
+}
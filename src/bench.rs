@@ -0,0 +1,219 @@
+// Timing of the solutions, compared against a saved baseline.
+//
+// A baseline file has one median per line: the day, the stage and the
+// time in nanoseconds, separated by spaces. Lines starting with `#` are
+// comments.
+//
+use crate::parse::ParseError;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    Parse,
+    Part(u8),
+}
+
+impl fmt::Display for Stage {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Stage::Parse => write!(f, "parse"),
+            Stage::Part(n) => write!(f, "part{}", n),
+        }
+    }
+}
+
+impl FromStr for Stage {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Stage, ()> {
+        match s {
+            "parse" => Ok(Stage::Parse),
+            _ => match s.strip_prefix("part").map(str::parse) {
+                Some(Ok(n)) => Ok(Stage::Part(n)),
+                _ => Err(()),
+            },
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Stats {
+    pub runs: usize,
+    pub mean: Duration,
+    pub median: Duration,
+    pub min: Duration,
+}
+
+impl Stats {
+    pub fn of(samples: &[Duration]) -> Stats {
+        assert!(!samples.is_empty(), "no samples");
+        let mut sorted = samples.to_vec();
+        sorted.sort();
+        let n = sorted.len();
+        let median = if n % 2 == 0 {
+            (sorted[n / 2 - 1] + sorted[n / 2]) / 2
+        } else {
+            sorted[n / 2]
+        };
+        Stats {
+            runs: n,
+            mean: sorted.iter().sum::<Duration>() / n as u32,
+            median,
+            min: sorted[0],
+        }
+    }
+
+    /// Units of work per second at the median time, e.g. Intcode steps
+    pub fn rate(&self, work: usize) -> f64 {
+        work as f64 / self.median.as_secs_f64()
+    }
+}
+
+/// Times `runs` calls of `f`, stopping at the first error
+pub fn measure<E, F>(runs: usize, mut f: F) -> Result<Stats, E>
+where
+    F: FnMut() -> Result<(), E>,
+{
+    let samples = (0..runs.max(1))
+        .map(|_| {
+            let start = Instant::now();
+            f()?;
+            Ok(start.elapsed())
+        })
+        .collect::<Result<Vec<_>, E>>()?;
+    Ok(Stats::of(&samples))
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Baseline {
+    medians: BTreeMap<(u8, Stage), Duration>,
+}
+
+impl Baseline {
+    pub fn new() -> Baseline {
+        Baseline::default()
+    }
+
+    pub fn parse(s: &str) -> Result<Baseline, ParseError> {
+        let mut baseline = Baseline::new();
+        for (i, line) in s.lines().enumerate() {
+            if line.trim().is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let day = field(i + 1, line, 0, "a day")?;
+            let stage = field(i + 1, line, 1, "a stage")?;
+            let nanos = field(i + 1, line, 2, "a time in nanoseconds")?;
+            baseline
+                .medians
+                .insert((day, stage), Duration::from_nanos(nanos));
+        }
+        Ok(baseline)
+    }
+
+    pub fn get(&self, day: u8, stage: Stage) -> Option<Duration> {
+        self.medians.get(&(day, stage)).copied()
+    }
+
+    pub fn insert(&mut self, day: u8, stage: Stage, stats: &Stats) {
+        self.medians.insert((day, stage), stats.median);
+    }
+
+    /// Relative change of the median against the baseline, `0.1` being
+    /// 10% slower
+    pub fn change(&self, day: u8, stage: Stage, stats: &Stats) -> Option<f64> {
+        let base = self.get(day, stage)?.as_secs_f64();
+        if base == 0.0 {
+            return None;
+        }
+        Some(stats.median.as_secs_f64() / base - 1.0)
+    }
+}
+
+/// Parses the `n`th space separated field of a line
+fn field<T: FromStr>(
+    line: usize,
+    text: &str,
+    n: usize,
+    expected: &'static str,
+) -> Result<T, ParseError> {
+    let mut column = 1;
+    for (i, field) in text.split(' ').enumerate() {
+        if i == n {
+            return field.parse().map_err(|_| ParseError {
+                line,
+                column,
+                token: field.to_string(),
+                expected,
+            });
+        }
+        column += field.chars().count() + 1;
+    }
+    Err(ParseError {
+        line,
+        column,
+        token: String::new(),
+        expected,
+    })
+}
+
+impl fmt::Display for Baseline {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "# day stage median-ns")?;
+        for ((day, stage), median) in &self.medians {
+            writeln!(f, "{} {} {}", day, stage, median.as_nanos())?;
+        }
+        Ok(())
+    }
+}
+
+#[test]
+fn test_stats() {
+    let ms = Duration::from_millis;
+    let stats = Stats::of(&[ms(4), ms(1), ms(10), ms(2)]);
+    assert_eq!(
+        stats,
+        Stats {
+            runs: 4,
+            mean: Duration::from_micros(4250),
+            median: Duration::from_micros(3000),
+            min: ms(1),
+        }
+    );
+    assert_eq!(Stats::of(&[ms(3), ms(1), ms(2)]).median, ms(2));
+    assert_eq!(stats.rate(600), 200_000.0);
+
+    let mut calls = 0;
+    assert_eq!(
+        measure(5, || {
+            calls += 1;
+            if calls == 3 {
+                Err("boom")
+            } else {
+                Ok(())
+            }
+        }),
+        Err("boom")
+    );
+    assert_eq!(measure(4, || Ok::<_, ()>(())).unwrap().runs, 4);
+}
+
+#[test]
+fn test_baseline() {
+    let mut baseline = Baseline::new();
+    let stats = Stats::of(&[Duration::from_millis(2)]);
+    baseline.insert(3, Stage::Parse, &stats);
+    baseline.insert(3, Stage::Part(2), &stats);
+    assert_eq!(Baseline::parse(&baseline.to_string()), Ok(baseline.clone()));
+
+    let slower = Stats::of(&[Duration::from_millis(3)]);
+    let change = baseline.change(3, Stage::Part(2), &slower).unwrap();
+    assert!((change - 0.5).abs() < 1e-9);
+    assert_eq!(baseline.change(3, Stage::Part(1), &slower), None);
+
+    let err = Baseline::parse("3 parse 100\n4 part1 fast\n").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (2, 9, "fast"));
+    assert!(Baseline::parse("4 part1\n").is_err());
+}
@@ -0,0 +1,723 @@
+// Runs the solutions of every day.
+//
+// Usage: aoc run <day|all> [--part <1|2>] [--input <path>|-] [--inputs <dir>]
+//        aoc verify [day|all] [--record] [--answers <path>] [--inputs <dir>]
+//        aoc bench [day|all] [--runs <n>] [--threshold <percent>] [--save]
+//                  [--baseline <path>] [--input <path>|-] [--inputs <dir>]
+//        aoc signals <table|dot> [--part <1|2>] [--input <path>|-] [--inputs <dir>]
+//        aoc symbolic [--input <path>|-] [--inputs <dir>]
+//        aoc cfg <day> [--input <path>|-] [--inputs <dir>]
+//        aoc throughput [--runs <n>] [--input <path>|-] [--inputs <dir>]
+//        aoc list
+//
+// Inputs default to `$AOC_INPUTS/inputN.txt`, see `aoc_2019::input`.
+// `verify` checks the answers against the `answers.txt` next to the
+// inputs, see `aoc_2019::answers`, and `--record` saves them there.
+// `bench` times parsing and each part, comparing the medians against a
+// baseline that `--save` writes, see `aoc_2019::bench`. Parts are timed
+// on input parsed beforehand, so their times leave parsing out. `cfg`
+// prints the control flow graph of an Intcode day's program in Graphviz
+// format.
+// `throughput` runs the Intcode program of day 9 part 2 with and without
+// the decoded instruction cache and reports the steps per second.
+// Exits with 1 when a part fails, changes or regresses and with 2 on usage
+// errors.
+//
+use aoc_2019::answers::{self, Answers, Verdict};
+use aoc_2019::bench::{self, Baseline, Stage, Stats};
+use aoc_2019::days::{self, SignalFormat, Solution};
+use aoc_2019::input::{self, Source};
+use aoc_2019::intcode::{self, ControlFlowGraph, IntCodeComputer, IntcodeError, QueueIo};
+use console::style;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "\
+usage: aoc run <day|all> [--part <1|2>] [--input <path>|-] [--inputs <dir>]
+       aoc verify [day|all] [--record] [--answers <path>] [--inputs <dir>]
+       aoc bench [day|all] [--runs <n>] [--threshold <percent>] [--save]
+                 [--baseline <path>] [--input <path>|-] [--inputs <dir>]
+       aoc signals <table|dot> [--part <1|2>] [--input <path>|-] [--inputs <dir>]
+       aoc symbolic [--input <path>|-] [--inputs <dir>]
+       aoc cfg <day> [--input <path>|-] [--inputs <dir>]
+       aoc throughput [--runs <n>] [--input <path>|-] [--inputs <dir>]
+       aoc list
+
+Inputs are read from --input, --inputs or the directory in $AOC_INPUTS.
+Answers default to answers.txt in the inputs directory.
+The benchmark baseline defaults to target/aoc-bench.txt.";
+
+/// Days whose input is an Intcode program
+const INTCODE_DAYS: [u8; 4] = [2, 5, 7, 9];
+
+/// Machine specific, so kept out of the tree
+const BASELINE: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/target/aoc-bench.txt");
+
+#[derive(Debug, PartialEq)]
+enum Command {
+    /// `None` runs every day
+    Run {
+        day: Option<u8>,
+        part: Option<u8>,
+        source: Source,
+    },
+    /// Checks every part against the answers file, or records it
+    Verify {
+        day: Option<u8>,
+        source: Source,
+        answers: PathBuf,
+        record: bool,
+    },
+    /// Times every stage, comparing against the baseline or saving it
+    Bench {
+        day: Option<u8>,
+        source: Source,
+        runs: usize,
+        /// Slowdown of the median that counts as a regression, `0.1` is 10%
+        threshold: f64,
+        baseline: PathBuf,
+        save: bool,
+    },
+    Signals {
+        format: SignalFormat,
+        part: u8,
+        source: Source,
+    },
+    /// Solves day 2 for the noun and verb with its closed form
+    Symbolic {
+        source: Source,
+    },
+    /// Prints the control flow graph of an Intcode program
+    Cfg {
+        day: u8,
+        source: Source,
+    },
+    /// Times the Intcode computer on day 9 part 2
+    Throughput {
+        runs: usize,
+        source: Source,
+    },
+    List,
+}
+
+fn parse_args(args: &[String]) -> Result<Command, String> {
+    let (cmd, rest) = args.split_first().ok_or("missing command")?;
+    let target = rest.first().filter(|t| !t.starts_with("--"));
+    let mut part = None;
+    let mut source = None;
+    let mut answers = None;
+    let mut record = false;
+    let mut runs = None;
+    let mut threshold = None;
+    let mut baseline = None;
+    let mut save = false;
+    let mut flags = rest.iter().skip(target.iter().count());
+    while let Some(flag) = flags.next() {
+        let mut value = || flags.next().ok_or(format!("{} needs a value", flag));
+        match flag.as_str() {
+            "--part" => match value()?.as_str() {
+                "1" => part = Some(1),
+                "2" => part = Some(2),
+                p => return Err(format!("invalid part {:?}", p)),
+            },
+            "--input" | "--inputs" if source.is_some() => {
+                return Err("only one of --input and --inputs can be given".to_string())
+            }
+            "--input" => source = Some(Source::from_arg(value()?)),
+            "--inputs" => source = Some(Source::Dir(value()?.into())),
+            "--answers" => answers = Some(PathBuf::from(value()?)),
+            "--record" => record = true,
+            "--runs" => match value()?.parse() {
+                Ok(n) if n > 0 => runs = Some(n),
+                _ => return Err("--runs needs a positive count".to_string()),
+            },
+            "--threshold" => match value()?.parse::<f64>() {
+                Ok(pct) if pct >= 0.0 => threshold = Some(pct / 100.0),
+                _ => return Err("--threshold needs a percentage".to_string()),
+            },
+            "--baseline" => baseline = Some(PathBuf::from(value()?)),
+            "--save" => save = true,
+            _ => return Err(format!("unknown option {:?}", flag)),
+        }
+    }
+    let single = matches!(source, Some(Source::File(_)) | Some(Source::Stdin));
+    let source = source.unwrap_or_else(Source::default_dir);
+
+    let day = |target: Option<&str>| match target {
+        None => Err(format!("{} needs a day or all", cmd)),
+        Some("all") if single => Err("--input needs a single day".to_string()),
+        Some("all") => Ok(None),
+        Some(d) => match d.parse().ok().and_then(days::get) {
+            Some(s) => Ok(Some(s.day())),
+            None => Err(format!("no solution for day {:?}", d)),
+        },
+    };
+    if cmd != "verify" && (answers.is_some() || record) {
+        return Err(format!("{} takes no --answers or --record", cmd));
+    }
+    let bench_flags = threshold.is_some() || baseline.is_some() || save;
+    if cmd != "bench" && (bench_flags || runs.is_some() && cmd != "throughput") {
+        return Err(format!("{} takes no benchmark options", cmd));
+    }
+
+    match cmd.as_str() {
+        "run" => Ok(Command::Run {
+            day: day(target.map(String::as_str))?,
+            part,
+            source,
+        }),
+        "verify" if part.is_some() => Err("verify checks both parts".to_string()),
+        "verify" => {
+            let answers = match (answers, &source) {
+                (Some(path), _) => path,
+                (None, Source::Dir(dir)) => dir.join(answers::FILE_NAME),
+                (None, _) => return Err("--input needs --answers".to_string()),
+            };
+            Ok(Command::Verify {
+                day: day(Some(target.map_or("all", String::as_str)))?,
+                source,
+                answers,
+                record,
+            })
+        }
+        "bench" if part.is_some() => Err("bench times both parts".to_string()),
+        "bench" => Ok(Command::Bench {
+            day: day(Some(target.map_or("all", String::as_str)))?,
+            source,
+            runs: runs.unwrap_or(10),
+            threshold: threshold.unwrap_or(0.1),
+            baseline: baseline.unwrap_or_else(|| BASELINE.into()),
+            save,
+        }),
+        "signals" => {
+            let format = match target.map(String::as_str) {
+                Some("table") => SignalFormat::Table,
+                Some("dot") => SignalFormat::Dot,
+                _ => return Err("signals needs table or dot".to_string()),
+            };
+            Ok(Command::Signals {
+                format,
+                part: part.unwrap_or(2),
+                source,
+            })
+        }
+        "symbolic" if target.is_none() && part.is_none() => Ok(Command::Symbolic { source }),
+        "cfg" if part.is_some() => Err("cfg takes no --part".to_string()),
+        "cfg" => match day(target.map(String::as_str))? {
+            Some(day) if INTCODE_DAYS.contains(&day) => Ok(Command::Cfg { day, source }),
+            _ => Err("cfg needs an Intcode day: 2, 5, 7 or 9".to_string()),
+        },
+        "throughput" if target.is_none() && part.is_none() => Ok(Command::Throughput {
+            runs: runs.unwrap_or(10),
+            source,
+        }),
+        "list" if rest.is_empty() => Ok(Command::List),
+        _ => Err(format!("unknown command {:?}", cmd)),
+    }
+}
+
+/// Prints the answers of a day, returning whether every part succeeded
+fn run_day(s: &dyn Solution, parts: &[u8], source: &Source) -> (bool, Duration) {
+    println!(
+        "{}",
+        style(format!("Day {}: {}", s.day(), s.title())).bold()
+    );
+    let input = match input::load(s.day(), source) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("  {}", style(format!("error: {}", e)).red());
+            return (false, Duration::default());
+        }
+    };
+    let mut ok = true;
+    let mut total = Duration::default();
+    for part in parts {
+        let start = Instant::now();
+        let answer = s.part(*part, &input);
+        let elapsed = start.elapsed();
+        total += elapsed;
+        let time = style(format!("({:.2?})", elapsed)).dim();
+        match answer {
+            Ok(answer) if answer.contains('\n') => {
+                println!("  Part {}: {}", part, time);
+                for line in answer.lines() {
+                    println!("    {}", line);
+                }
+            }
+            Ok(answer) => println!("  Part {}: {}  {}", part, answer, time),
+            Err(e) => {
+                ok = false;
+                println!(
+                    "  Part {}: {}  {}",
+                    part,
+                    style(format!("error: {}: {}", source.describe(s.day()), e)).red(),
+                    time
+                );
+            }
+        }
+    }
+    (ok, total)
+}
+
+/// Prints the lines of an answer below its part
+fn print_lines(label: &str, answer: &str) {
+    println!("    {}", label);
+    for line in answer.lines() {
+        println!("      {}", line);
+    }
+}
+
+/// Checks both parts of a day, recording the answers into `record`
+fn verify_day(
+    s: &dyn Solution,
+    source: &Source,
+    answers: &Answers,
+    record: &mut Answers,
+) -> Vec<Verdict> {
+    println!(
+        "{}",
+        style(format!("Day {}: {}", s.day(), s.title())).bold()
+    );
+    let input = match input::load(s.day(), source) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("  {}", style(format!("FAIL: {}", e)).red());
+            return vec![Verdict::Failed(e.to_string())];
+        }
+    };
+    let mut verdicts = vec![];
+    for part in 1..=2 {
+        let start = Instant::now();
+        let answer = s.part(part, &input);
+        let time = style(format!("({:.2?})", start.elapsed())).dim();
+        let verdict = answers.check(s.day(), part, &input, &answer);
+        match (&verdict, &answer) {
+            (Verdict::Pass, _) => {
+                println!("  Part {}: {}  {}", part, style(&verdict).green(), time)
+            }
+            (Verdict::Changed { expected }, Ok(answer))
+                if expected.contains('\n') || answer.contains('\n') =>
+            {
+                println!("  Part {}: {}  {}", part, style(&verdict).yellow(), time);
+                print_lines("expected:", expected);
+                print_lines("got:", answer);
+            }
+            (Verdict::Changed { expected }, Ok(answer)) => println!(
+                "  Part {}: {}  {}",
+                part,
+                style(format!(
+                    "{}: expected {}, got {}",
+                    verdict, expected, answer
+                ))
+                .yellow(),
+                time
+            ),
+            (Verdict::Failed(e), _) => println!(
+                "  Part {}: {}  {}",
+                part,
+                style(format!("{}: {}: {}", verdict, source.describe(s.day()), e)).red(),
+                time
+            ),
+            (_, Ok(answer)) if answer.contains('\n') => {
+                println!("  Part {}: {}  {}", part, style(&verdict).dim(), time);
+                print_lines("answer:", answer);
+            }
+            (_, Ok(answer)) => println!(
+                "  Part {}: {} {}  {}",
+                part,
+                style(&verdict).dim(),
+                answer,
+                time
+            ),
+            (_, Err(_)) => unreachable!(),
+        }
+        if let Ok(answer) = &answer {
+            record.record(s.day(), part, &input, answer);
+        }
+        verdicts.push(verdict);
+    }
+    verdicts
+}
+
+/// Times the stages of a day, returning how many regressed or `None` when
+/// one failed
+fn bench_day(
+    s: &dyn Solution,
+    source: &Source,
+    runs: usize,
+    threshold: f64,
+    baseline: &Baseline,
+    saved: &mut Baseline,
+) -> Option<usize> {
+    println!(
+        "{}",
+        style(format!("Day {}: {}", s.day(), s.title())).bold()
+    );
+    let input = match input::load(s.day(), source) {
+        Ok(input) => input,
+        Err(e) => {
+            println!("  {}", style(format!("error: {}", e)).red());
+            return None;
+        }
+    };
+    let fail = |label: &str, e: Box<dyn Error>| {
+        println!(
+            "  {:<7} {}",
+            label,
+            style(format!("error: {}: {}", source.describe(s.day()), e)).red()
+        );
+    };
+    let parsed = match s.parse(&input) {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            fail("parse", e);
+            return None;
+        }
+    };
+    let mut regressions = 0;
+    for stage in [Stage::Parse, Stage::Part(1), Stage::Part(2)] {
+        let stats = match stage {
+            Stage::Parse => bench::measure(runs, || s.parse(&input).map(drop)),
+            Stage::Part(part) => bench::measure(runs, || s.solve(part, &parsed).map(drop)),
+        };
+        let label = match stage {
+            Stage::Parse => "parse".to_string(),
+            Stage::Part(part) => format!("part {}", part),
+        };
+        let stats = match stats {
+            Ok(stats) => stats,
+            Err(e) => {
+                fail(&label, e);
+                return None;
+            }
+        };
+        let change = match baseline.change(s.day(), stage, &stats) {
+            None => style("new".to_string()).dim(),
+            Some(change) if change > threshold => {
+                regressions += 1;
+                style(format!("{:+.1}% regressed", change * 100.0)).red()
+            }
+            Some(change) if change < -threshold => {
+                style(format!("{:+.1}%", change * 100.0)).green()
+            }
+            Some(change) => style(format!("{:+.1}%", change * 100.0)).dim(),
+        };
+        println!("  {:<7} {}  {}", label, summary(&stats), change);
+        saved.insert(s.day(), stage, &stats);
+    }
+    Some(regressions)
+}
+
+/// Times day 9 part 2 with the decoded instruction cache off, then on
+fn throughput(source: &Source, runs: usize) -> Result<(), String> {
+    let input = input::load(9, source).map_err(|e| e.to_string())?;
+    let prog =
+        intcode::parse_program(&input).map_err(|e| format!("{}: {}", source.describe(9), e))?;
+    println!("{}", style("Day 9 part 2").bold());
+    let mut base = None;
+    for cache in [false, true] {
+        let mut steps = 0;
+        let stats = bench::measure(runs, || {
+            let mut c = IntCodeComputer::with_io(prog.clone(), QueueIo::new(vec![2]));
+            c.with_decode_cache(cache).run()?;
+            steps = c.steps();
+            Ok(())
+        })
+        .map_err(|e: IntcodeError| format!("{}: {}", source.describe(9), e))?;
+        let rate = stats.rate(steps);
+        let change = match base {
+            Some(base) => format!("  {:+.1}%", (rate / base - 1.0) * 100.0),
+            None => String::new(),
+        };
+        println!(
+            "  cache {:<3} {} steps  median {:>10.2?}  {:>6.1}M steps/s{}",
+            if cache { "on" } else { "off" },
+            steps,
+            stats.median,
+            rate / 1e6,
+            change
+        );
+        base = Some(rate);
+    }
+    Ok(())
+}
+
+fn summary(stats: &Stats) -> String {
+    format!(
+        "mean {:>10.2?}  median {:>10.2?}  min {:>10.2?}",
+        stats.mean, stats.median, stats.min
+    )
+}
+
+fn load_baseline(path: &Path) -> Result<Baseline, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Baseline::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(Baseline::new()),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn load_answers(path: &Path, record: bool) -> Result<Answers, String> {
+    match fs::read_to_string(path) {
+        Ok(text) => Answers::parse(&text).map_err(|e| format!("{}: {}", path.display(), e)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound && record => Ok(Answers::new()),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Err(format!(
+            "{}: no answers recorded; run aoc verify --record first",
+            path.display()
+        )),
+        Err(e) => Err(format!("{}: {}", path.display(), e)),
+    }
+}
+
+fn run(cmd: Command) -> Result<bool, String> {
+    match cmd {
+        Command::List => {
+            for s in days::all() {
+                println!("{:>2}  {}", s.day(), s.title());
+            }
+            Ok(true)
+        }
+        Command::Signals {
+            format,
+            part,
+            source,
+        } => {
+            let input = input::load(7, &source).map_err(|e| e.to_string())?;
+            let out = days::render_signals(&input, part, format)
+                .map_err(|e| format!("{}: {}", source.describe(7), e))?;
+            print!("{}", out);
+            Ok(true)
+        }
+        Command::Verify {
+            day,
+            source,
+            answers: path,
+            record,
+        } => {
+            let answers = load_answers(&path, record)?;
+            let mut recorded = answers.clone();
+            let solutions = match day {
+                Some(day) => vec![days::get(day).unwrap()],
+                None => days::all().to_vec(),
+            };
+            let verdicts = solutions
+                .iter()
+                .flat_map(|s| verify_day(*s, &source, &answers, &mut recorded))
+                .collect::<Vec<_>>();
+            let count = |f: fn(&Verdict) -> bool| verdicts.iter().filter(|v| f(v)).count();
+            let passed = count(|v| *v == Verdict::Pass);
+            let changed = count(|v| matches!(v, Verdict::Changed { .. }));
+            let failed = count(|v| matches!(v, Verdict::Failed(_)));
+            let unknown = count(|v| *v == Verdict::Unknown);
+            println!(
+                "{}",
+                style(format!(
+                    "{} passed, {} changed, {} failed, {} unknown",
+                    passed, changed, failed, unknown
+                ))
+                .bold()
+            );
+            if record {
+                fs::write(&path, recorded.to_string())
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                println!("Recorded {} answers to {}", recorded.len(), path.display());
+                return Ok(failed == 0);
+            }
+            Ok(changed == 0 && failed == 0)
+        }
+        Command::Symbolic { source } => {
+            let input = input::load(2, &source).map_err(|e| e.to_string())?;
+            let out = days::explain_noun_verb(&input)
+                .map_err(|e| format!("{}: {}", source.describe(2), e))?;
+            print!("{}", out);
+            Ok(true)
+        }
+        Command::Throughput { runs, source } => throughput(&source, runs).map(|_| true),
+        Command::Cfg { day, source } => {
+            let input = input::load(day, &source).map_err(|e| e.to_string())?;
+            let prog = intcode::parse_program(&input)
+                .map_err(|e| format!("{}: {}", source.describe(day), e))?;
+            print!("{}", ControlFlowGraph::build(&prog).dot());
+            Ok(true)
+        }
+        Command::Bench {
+            day,
+            source,
+            runs,
+            threshold,
+            baseline: path,
+            save,
+        } => {
+            let baseline = load_baseline(&path)?;
+            let mut saved = baseline.clone();
+            let solutions = match day {
+                Some(day) => vec![days::get(day).unwrap()],
+                None => days::all().to_vec(),
+            };
+            let mut failed = 0;
+            let mut regressions = 0;
+            for s in &solutions {
+                match bench_day(*s, &source, runs, threshold, &baseline, &mut saved) {
+                    Some(n) => regressions += n,
+                    None => failed += 1,
+                }
+            }
+            println!(
+                "{}",
+                style(format!(
+                    "{} runs each, {} regressed beyond {}%, {} failed",
+                    runs,
+                    regressions,
+                    threshold * 100.0,
+                    failed
+                ))
+                .bold()
+            );
+            if save {
+                if let Some(dir) = path.parent() {
+                    fs::create_dir_all(dir).map_err(|e| format!("{}: {}", dir.display(), e))?;
+                }
+                fs::write(&path, saved.to_string())
+                    .map_err(|e| format!("{}: {}", path.display(), e))?;
+                println!("Saved baseline to {}", path.display());
+                return Ok(failed == 0);
+            }
+            Ok(regressions == 0 && failed == 0)
+        }
+        Command::Run { day, part, source } => {
+            let parts = part.map_or(vec![1, 2], |p| vec![p]);
+            let solutions = match day {
+                Some(day) => vec![days::get(day).unwrap()],
+                None => days::all().to_vec(),
+            };
+            let mut ok = true;
+            let mut total = Duration::default();
+            for s in &solutions {
+                let (day_ok, elapsed) = run_day(*s, &parts, &source);
+                ok &= day_ok;
+                total += elapsed;
+            }
+            if solutions.len() > 1 {
+                println!("{}", style(format!("Total: {:.2?}", total)).bold());
+            }
+            Ok(ok)
+        }
+    }
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let cmd = match parse_args(&args) {
+        Ok(cmd) => cmd,
+        Err(e) => {
+            eprintln!("aoc: {}\n{}", e, USAGE);
+            std::process::exit(2);
+        }
+    };
+    match run(cmd) {
+        Ok(true) => {}
+        Ok(false) => std::process::exit(1),
+        Err(e) => {
+            eprintln!("aoc: {}", e);
+            std::process::exit(1);
+        }
+    }
+}
+
+#[test]
+fn test_parse_args() {
+    let parse = |s: &str| parse_args(&s.split_whitespace().map(String::from).collect::<Vec<_>>());
+    assert_eq!(
+        parse("run 7 --part 2 --input in.txt"),
+        Ok(Command::Run {
+            day: Some(7),
+            part: Some(2),
+            source: Source::File("in.txt".into())
+        })
+    );
+    assert_eq!(
+        parse("run all --inputs theirs"),
+        Ok(Command::Run {
+            day: None,
+            part: None,
+            source: Source::Dir("theirs".into())
+        })
+    );
+    assert_eq!(
+        parse("signals dot --input -"),
+        Ok(Command::Signals {
+            format: SignalFormat::Dot,
+            part: 2,
+            source: Source::Stdin
+        })
+    );
+    assert_eq!(parse("list"), Ok(Command::List));
+    assert!(parse("").is_err());
+    assert!(parse("run 42").is_err());
+    assert!(parse("run 3 --part 3").is_err());
+    assert!(parse("run 3 --input").is_err());
+    assert!(parse("run all --input in.txt").is_err());
+    assert!(parse("run 1 --input in.txt --inputs dir").is_err());
+    assert_eq!(
+        parse("verify --inputs theirs --record"),
+        Ok(Command::Verify {
+            day: None,
+            source: Source::Dir("theirs".into()),
+            answers: PathBuf::from("theirs").join("answers.txt"),
+            record: true
+        })
+    );
+    assert_eq!(
+        parse("verify 5 --input - --answers known.txt"),
+        Ok(Command::Verify {
+            day: Some(5),
+            source: Source::Stdin,
+            answers: "known.txt".into(),
+            record: false
+        })
+    );
+    assert!(parse("verify 5 --input -").is_err());
+    assert!(parse("verify --part 1").is_err());
+    assert!(parse("run 1 --record").is_err());
+    assert_eq!(
+        parse("bench 3 --runs 5 --threshold 25 --baseline base.txt --save"),
+        Ok(Command::Bench {
+            day: Some(3),
+            source: Source::default_dir(),
+            runs: 5,
+            threshold: 0.25,
+            baseline: "base.txt".into(),
+            save: true
+        })
+    );
+    assert!(parse("bench --runs 0").is_err());
+    assert!(parse("bench --part 1").is_err());
+    assert!(parse("run 3 --runs 5").is_err());
+    assert_eq!(
+        parse("symbolic --input -"),
+        Ok(Command::Symbolic {
+            source: Source::Stdin
+        })
+    );
+    assert!(parse("symbolic 2").is_err());
+    assert_eq!(
+        parse("cfg 9"),
+        Ok(Command::Cfg {
+            day: 9,
+            source: Source::default_dir()
+        })
+    );
+    assert!(parse("cfg 3").is_err());
+    assert!(parse("cfg all").is_err());
+    assert_eq!(
+        parse("throughput --runs 3"),
+        Ok(Command::Throughput {
+            runs: 3,
+            source: Source::default_dir()
+        })
+    );
+    assert!(parse("cfg 9 --runs 3").is_err());
+}
@@ -0,0 +1,243 @@
+// Interactive step debugger for Intcode programs.
+//
+// Usage: intcode-debugger <program> [input...]
+//
+use aoc_2019::intcode::{
+    decode_at, parse_program, Debugger, Entry, IntcodeError, LargeNumber, Stop,
+};
+use std::io::{BufRead, Write};
+
+const HELP: &str = "\
+s, step [n]          execute n instructions (default 1)
+c, continue          run until a breakpoint, watchpoint, halt or missing input
+b, break <addr>      set a breakpoint
+d, delete <addr>     remove a breakpoint
+w, watch <addr>      stop when a memory cell changes
+u, unwatch <addr>    remove a watchpoint
+r, regs              show registers, breakpoints and watchpoints
+x, mem <from> [to]   dump memory cells
+l, list [addr] [n]   disassemble n instructions (default: 5 from pc)
+i, input <value>...  queue program input
+q, quit";
+
+/// Memory cells shown per line of a dump
+const CELLS_PER_LINE: usize = 8;
+
+fn current(dbg: &Debugger) -> String {
+    list(dbg, dbg.computer.pc, 1)
+}
+
+fn list(dbg: &Debugger, mut addr: usize, count: usize) -> String {
+    let mut lines = vec![];
+    for _ in 0..count {
+        let entry = decode_at(&dbg.computer.mem, addr).unwrap_or(Entry::Data {
+            addr,
+            values: vec![dbg.cell(addr)],
+        });
+        let marker = if addr == dbg.computer.pc { "=>" } else { "  " };
+        lines.push(format!("{} {}", marker, entry));
+        addr += entry.size();
+    }
+    lines.join("\n")
+}
+
+fn dump(dbg: &Debugger, from: usize, to: usize) -> String {
+    let mut lines = vec![];
+    let mut addr = from;
+    while addr <= to {
+        let end = to.min(addr + CELLS_PER_LINE - 1);
+        let cells = (addr..=end)
+            .map(|a| dbg.cell(a).to_string())
+            .collect::<Vec<_>>();
+        lines.push(format!("{:04}: {}", addr, cells.join(" ")));
+        addr = end + 1;
+    }
+    lines.join("\n")
+}
+
+fn regs(dbg: &Debugger) -> String {
+    format!(
+        "pc: {}  relbase: {}  steps: {}  state: {:?}\nbreakpoints: {:?}\nwatchpoints: {:?}",
+        dbg.computer.pc,
+        dbg.computer.relbase(),
+        dbg.computer.steps(),
+        dbg.computer.state,
+        dbg.breakpoints(),
+        dbg.watchpoints(),
+    )
+}
+
+fn describe(dbg: &Debugger, stop: Result<Stop, IntcodeError>) -> String {
+    let reason = match stop {
+        Ok(Stop::Stepped) => None,
+        Ok(Stop::Breakpoint(addr)) => Some(format!("breakpoint at {}", addr)),
+        Ok(Stop::Watchpoint { addr, old, new }) => {
+            Some(format!("watchpoint [{}]: {} -> {}", addr, old, new))
+        }
+        Ok(Stop::NeedsInput) => Some("waiting for input".to_string()),
+        Ok(Stop::Halted) => Some("halted".to_string()),
+        Err(e) => Some(format!("error: {}", e)),
+    };
+    let mut lines = vec![];
+    lines.extend(reason);
+    lines.push(current(dbg));
+    lines.join("\n")
+}
+
+fn addr_arg(arg: Option<&str>) -> Result<usize, String> {
+    let arg = arg.ok_or("missing address")?;
+    arg.parse()
+        .map_err(|_| format!("invalid address `{}`", arg))
+}
+
+/// Runs a single REPL command, returning `None` on quit
+fn command(dbg: &mut Debugger, line: &str) -> Option<String> {
+    let mut words = line.split_whitespace();
+    let cmd = match words.next() {
+        Some(cmd) => cmd,
+        None => return Some(String::new()),
+    };
+    let args = words.collect::<Vec<_>>();
+    let arg = |i: usize| args.get(i).copied();
+
+    let result = match cmd {
+        "s" | "step" => arg(0)
+            .map_or(Ok(1), |n| {
+                n.parse().map_err(|_| format!("invalid count `{}`", n))
+            })
+            .map(|n: usize| {
+                let mut stop = Ok(Stop::Stepped);
+                for _ in 0..n {
+                    stop = dbg.step();
+                    if stop != Ok(Stop::Stepped) {
+                        break;
+                    }
+                }
+                describe(dbg, stop)
+            }),
+        "c" | "continue" => {
+            let stop = dbg.cont();
+            Ok(describe(dbg, stop))
+        }
+        "b" | "break" => addr_arg(arg(0)).map(|a| {
+            dbg.add_breakpoint(a);
+            format!("breakpoint at {}", a)
+        }),
+        "d" | "delete" => addr_arg(arg(0)).map(|a| match dbg.remove_breakpoint(a) {
+            true => format!("removed breakpoint at {}", a),
+            false => format!("no breakpoint at {}", a),
+        }),
+        "w" | "watch" => addr_arg(arg(0)).map(|a| {
+            dbg.add_watchpoint(a);
+            format!("watching [{}] = {}", a, dbg.cell(a))
+        }),
+        "u" | "unwatch" => addr_arg(arg(0)).map(|a| match dbg.remove_watchpoint(a) {
+            true => format!("removed watchpoint on [{}]", a),
+            false => format!("no watchpoint on [{}]", a),
+        }),
+        "r" | "regs" => Ok(regs(dbg)),
+        "x" | "mem" => addr_arg(arg(0)).and_then(|from| {
+            let to = arg(1).map_or(Ok(from), |a| addr_arg(Some(a)))?;
+            Ok(dump(dbg, from, to))
+        }),
+        "l" | "list" => {
+            let from = arg(0).map_or(Ok(dbg.computer.pc), |a| addr_arg(Some(a)));
+            let count = arg(1).map_or(Ok(5), |a| addr_arg(Some(a)));
+            from.and_then(|from| Ok(list(dbg, from, count?)))
+        }
+        "i" | "input" => args
+            .iter()
+            .map(|v| {
+                v.parse::<LargeNumber>()
+                    .map_err(|_| format!("invalid input `{}`", v))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .map(|values| {
+                dbg.computer.io.input.extend(values);
+                format!("queued input: {:?}", dbg.computer.io.input)
+            }),
+        "h" | "help" => Ok(HELP.to_string()),
+        "q" | "quit" => return None,
+        _ => Err(format!("unknown command `{}`, try `help`", cmd)),
+    };
+
+    let mut lines = dbg
+        .computer
+        .io
+        .output
+        .drain(..)
+        .map(|value| format!("output: {}", value))
+        .collect::<Vec<_>>();
+    lines.push(result.unwrap_or_else(|e| e));
+    Some(lines.join("\n"))
+}
+
+fn main() {
+    let args = std::env::args().skip(1).collect::<Vec<_>>();
+    let path = match args.first() {
+        Some(path) => path,
+        None => {
+            eprintln!("usage: intcode-debugger <program> [input...]");
+            std::process::exit(2);
+        }
+    };
+    let src = match std::fs::read_to_string(path) {
+        Ok(src) => src,
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    let mut dbg = match parse_program(&src) {
+        Ok(prog) => Debugger::new(prog),
+        Err(e) => {
+            eprintln!("{}: {}", path, e);
+            std::process::exit(1);
+        }
+    };
+    if args.len() > 1 {
+        if let Some(out) = command(&mut dbg, &format!("input {}", args[1..].join(" "))) {
+            println!("{}", out);
+        }
+    }
+    println!("{}", current(&dbg));
+
+    let stdin = std::io::stdin();
+    loop {
+        print!("> ");
+        std::io::stdout().flush().unwrap();
+        let mut line = String::new();
+        if stdin.lock().read_line(&mut line).unwrap_or(0) == 0 {
+            break;
+        }
+        match command(&mut dbg, &line) {
+            Some(out) => println!("{}", out),
+            None => break,
+        }
+    }
+}
+
+#[test]
+fn test_commands() {
+    let mut dbg = Debugger::new(vec![3, 9, 1001, 9, 1, 9, 4, 9, 99, 0]);
+    assert_eq!(
+        command(&mut dbg, "c").unwrap(),
+        "waiting for input\n=> 0000: STO [9]"
+    );
+    command(&mut dbg, "input 41");
+    command(&mut dbg, "watch 9");
+    assert_eq!(
+        command(&mut dbg, "c").unwrap(),
+        "watchpoint [9]: 0 -> 41\n=> 0002: ADD [9], #1, [9]"
+    );
+    assert_eq!(command(&mut dbg, "x 8 9").unwrap(), "0008: 99 41");
+    assert_eq!(
+        command(&mut dbg, "s 3").unwrap(),
+        "watchpoint [9]: 41 -> 42\n=> 0006: LOAD [9]"
+    );
+    assert_eq!(
+        command(&mut dbg, "c").unwrap(),
+        "output: 42\nhalted\n=> 0008: HLT"
+    );
+    assert_eq!(command(&mut dbg, "q"), None);
+}
@@ -0,0 +1,222 @@
+// Lazy permutations, combinations and cartesian products.
+//
+// Every iterator yields owned `Vec`s one at a time, so searches over
+// large candidate sets never hold more than the current one in memory.
+//
+
+/// Every ordering of `items`, in the order of Heap's algorithm
+pub fn permutations<T: Clone>(items: Vec<T>) -> Permutations<T> {
+    Permutations {
+        c: vec![0; items.len()],
+        items,
+        i: 0,
+        started: false,
+    }
+}
+
+/// Every ordered selection of `k` of the `items`
+pub fn k_permutations<T: Clone>(items: Vec<T>, k: usize) -> KPermutations<T> {
+    KPermutations {
+        combinations: combinations(items, k),
+        current: None,
+    }
+}
+
+/// Every unordered selection of `k` of the `items`, in lexicographic
+/// order of their positions
+pub fn combinations<T: Clone>(items: Vec<T>, k: usize) -> Combinations<T> {
+    Combinations {
+        done: k > items.len(),
+        indices: (0..k).collect(),
+        items,
+    }
+}
+
+/// Every way to pick one value from each pool, the last pool varying fastest
+pub fn product<T: Clone>(pools: Vec<Vec<T>>) -> Product<T> {
+    Product {
+        done: pools.iter().any(|p| p.is_empty()),
+        indices: vec![0; pools.len()],
+        pools,
+    }
+}
+
+pub struct Permutations<T> {
+    items: Vec<T>,
+    c: Vec<usize>,
+    i: usize,
+    started: bool,
+}
+
+impl<T: Clone> Iterator for Permutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if !self.started {
+            self.started = true;
+            return Some(self.items.clone());
+        }
+        while self.i < self.items.len() {
+            let i = self.i;
+            if self.c[i] < i {
+                if i % 2 == 0 {
+                    self.items.swap(0, i);
+                } else {
+                    self.items.swap(self.c[i], i);
+                }
+                self.c[i] += 1;
+                self.i = 0;
+                return Some(self.items.clone());
+            }
+            self.c[i] = 0;
+            self.i += 1;
+        }
+        None
+    }
+}
+
+pub struct KPermutations<T> {
+    combinations: Combinations<T>,
+    current: Option<Permutations<T>>,
+}
+
+impl<T: Clone> Iterator for KPermutations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        loop {
+            if let Some(p) = self.current.as_mut().and_then(Iterator::next) {
+                return Some(p);
+            }
+            self.current = Some(permutations(self.combinations.next()?));
+        }
+    }
+}
+
+pub struct Combinations<T> {
+    items: Vec<T>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Combinations<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let res = self
+            .indices
+            .iter()
+            .map(|i| self.items[*i].clone())
+            .collect();
+        let (n, k) = (self.items.len(), self.indices.len());
+        match (0..k).rev().find(|i| self.indices[*i] != i + n - k) {
+            Some(i) => {
+                self.indices[i] += 1;
+                for j in i + 1..k {
+                    self.indices[j] = self.indices[j - 1] + 1;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(res)
+    }
+}
+
+pub struct Product<T> {
+    pools: Vec<Vec<T>>,
+    indices: Vec<usize>,
+    done: bool,
+}
+
+impl<T: Clone> Iterator for Product<T> {
+    type Item = Vec<T>;
+
+    fn next(&mut self) -> Option<Vec<T>> {
+        if self.done {
+            return None;
+        }
+        let res = self
+            .indices
+            .iter()
+            .zip(&self.pools)
+            .map(|(i, pool)| pool[*i].clone())
+            .collect();
+        match (0..self.pools.len())
+            .rev()
+            .find(|p| self.indices[*p] + 1 < self.pools[*p].len())
+        {
+            Some(p) => {
+                self.indices[p] += 1;
+                for i in self.indices[p + 1..].iter_mut() {
+                    *i = 0;
+                }
+            }
+            None => self.done = true,
+        }
+        Some(res)
+    }
+}
+
+#[test]
+fn test_permutations() {
+    let perms = permutations(vec![1, 2, 3]).collect::<Vec<_>>();
+    assert_eq!(
+        perms,
+        vec![
+            vec![1, 2, 3],
+            vec![2, 1, 3],
+            vec![3, 1, 2],
+            vec![1, 3, 2],
+            vec![2, 3, 1],
+            vec![3, 2, 1]
+        ]
+    );
+    assert_eq!(permutations(vec![0; 5]).count(), 120);
+    assert_eq!(permutations(Vec::<u8>::new()).count(), 1);
+
+    assert_eq!(k_permutations(vec![1, 2, 3, 4], 2).count(), 12);
+    let mut kperms = k_permutations(vec!['a', 'b', 'c'], 2).collect::<Vec<_>>();
+    kperms.sort();
+    assert_eq!(
+        kperms[..3],
+        [vec!['a', 'b'], vec!['a', 'c'], vec!['b', 'a']]
+    );
+    assert_eq!(k_permutations(vec![1, 2], 3).next(), None);
+}
+
+#[test]
+fn test_combinations_and_product() {
+    assert_eq!(
+        combinations(vec![1, 2, 3, 4], 2).collect::<Vec<_>>(),
+        vec![
+            vec![1, 2],
+            vec![1, 3],
+            vec![1, 4],
+            vec![2, 3],
+            vec![2, 4],
+            vec![3, 4]
+        ]
+    );
+    assert_eq!(combinations(vec![1, 2, 3], 0).count(), 1);
+    assert_eq!(combinations(vec![1, 2, 3], 4).count(), 0);
+
+    assert_eq!(
+        product(vec![vec![0, 1], vec![5, 6, 7]]).collect::<Vec<_>>(),
+        vec![
+            vec![0, 5],
+            vec![0, 6],
+            vec![0, 7],
+            vec![1, 5],
+            vec![1, 6],
+            vec![1, 7]
+        ]
+    );
+    assert_eq!(product(vec![vec![1], vec![]]).count(), 0);
+    assert_eq!(
+        product(vec![(0..100).collect::<Vec<_>>(); 2]).count(),
+        10_000
+    );
+}
@@ -0,0 +1,85 @@
+// Solutions to every day, behind one interface for the `aoc` runner.
+//
+mod day1;
+mod day2;
+mod day3;
+mod day4;
+mod day5;
+mod day6;
+mod day7;
+mod day8;
+mod day9;
+
+pub use day2::explain_noun_verb;
+pub use day7::{render_signals, SignalFormat};
+
+use std::any::Any;
+use std::error::Error;
+
+pub type Answer = Result<String, Box<dyn Error>>;
+
+/// A day's input once parsed, only the day that parsed it can solve it
+pub type Parsed = Box<dyn Any>;
+
+/// The input as parsed by the current day
+fn parsed<T: 'static>(input: &Parsed) -> Result<&T, Box<dyn Error>> {
+    input
+        .downcast_ref()
+        .ok_or_else(|| "input was parsed by another day".into())
+}
+
+pub trait Solution: Sync {
+    fn day(&self) -> u8;
+    fn title(&self) -> &'static str;
+    /// Parses the input once, for both parts
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>>;
+    fn part_one(&self, input: &Parsed) -> Answer;
+    fn part_two(&self, input: &Parsed) -> Answer;
+
+    /// Parses the input and solves a part of it
+    fn part(&self, part: u8, input: &str) -> Answer {
+        self.solve(part, &self.parse(input)?)
+    }
+
+    fn solve(&self, part: u8, input: &Parsed) -> Answer {
+        match part {
+            1 => self.part_one(input),
+            2 => self.part_two(input),
+            _ => Err(format!("day {} has no part {}", self.day(), part).into()),
+        }
+    }
+}
+
+static SOLUTIONS: [&dyn Solution; 9] = [
+    &day1::Day1,
+    &day2::Day2,
+    &day3::Day3,
+    &day4::Day4,
+    &day5::Day5,
+    &day6::Day6,
+    &day7::Day7,
+    &day8::Day8,
+    &day9::Day9,
+];
+
+/// Every solution in day order
+pub fn all() -> &'static [&'static dyn Solution] {
+    &SOLUTIONS
+}
+
+pub fn get(day: u8) -> Option<&'static dyn Solution> {
+    SOLUTIONS.iter().copied().find(|s| s.day() == day)
+}
+
+#[test]
+fn test_registry() {
+    for (i, s) in all().iter().enumerate() {
+        assert_eq!(s.day() as usize, i + 1);
+    }
+    assert_eq!(get(7).map(|s| s.title()), Some("Amplification Circuit"));
+    assert!(get(10).is_none());
+    assert!(get(1).unwrap().part(3, "").is_err());
+    let parsed = get(1).unwrap().parse("12").unwrap();
+    assert_eq!(get(1).unwrap().solve(1, &parsed).unwrap(), "2");
+    assert!(get(4).unwrap().solve(1, &parsed).is_err());
+}
@@ -0,0 +1,72 @@
+// --- Day 1: The Tyranny of the Rocket Equation ---
+//
+// https://adventofcode.com/2019/day/1
+
+use super::{parsed, Answer, Parsed, Solution};
+use crate::parse::{self, ParseError};
+use std::error::Error;
+
+fn calc_fuel(mass: i32) -> i32 {
+    (mass / 3) - 2
+}
+
+fn calc_fuel_integral(mass: i32) -> i32 {
+    let fuel_mass = calc_fuel(mass);
+    if fuel_mass < 1 {
+        0
+    } else {
+        fuel_mass + calc_fuel_integral(fuel_mass)
+    }
+}
+
+fn masses(input: &str) -> Result<Vec<i32>, ParseError> {
+    parse::per_line(input)
+}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    fn day(&self) -> u8 {
+        1
+    }
+
+    fn title(&self) -> &'static str {
+        "The Tyranny of the Rocket Equation"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(masses(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<Vec<i32>>(input)?
+            .iter()
+            .map(|m| calc_fuel(*m))
+            .sum::<i32>()
+            .to_string())
+    }
+
+    fn part_two(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<Vec<i32>>(input)?
+            .iter()
+            .map(|m| calc_fuel_integral(*m))
+            .sum::<i32>()
+            .to_string())
+    }
+}
+
+#[test]
+fn test_calc_fuel() {
+    assert_eq!(calc_fuel(12), 2);
+    assert_eq!(calc_fuel(14), 2);
+    assert_eq!(calc_fuel(1969), 654);
+    assert_eq!(calc_fuel(100756), 33583);
+}
+
+#[test]
+fn test_calc_fuel_integral() {
+    assert_eq!(calc_fuel_integral(12), 2);
+    assert_eq!(calc_fuel_integral(14), 2);
+    assert_eq!(calc_fuel_integral(1969), 966);
+    assert_eq!(calc_fuel_integral(100756), 50346);
+}
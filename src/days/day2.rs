@@ -0,0 +1,270 @@
+// --- Day 2: 1202 Program Alarm ---
+//
+// https://adventofcode.com/2019/day/2
+//
+
+use super::{parsed, Answer, Parsed, Solution};
+use crate::combinatorics::product;
+use crate::intcode::{
+    check, parse_program, Check, Constraint, End, Expr, IntCodeComputer, IntcodeError, LargeNumber,
+    Limits, Memory, QueueIo, Symbolic, Var,
+};
+use std::error::Error;
+use std::fmt;
+
+/// Instructions a run may take before it is taken to be looping
+const STEP_LIMIT: usize = 100_000;
+
+/// Runs the program with no input, failing past `STEP_LIMIT` steps
+fn run_intcode(memory: Memory) -> Result<Memory, IntcodeError> {
+    let mut computer = IntCodeComputer::with_io(memory, QueueIo::default());
+    computer.with_limits(Limits {
+        steps: Some(STEP_LIMIT),
+        ..Limits::default()
+    });
+    computer.run()?;
+    Ok(computer.mem)
+}
+
+/// Runs the program with the noun and verb in cells 1 and 2
+fn run_with(
+    memory: &Memory,
+    noun: LargeNumber,
+    verb: LargeNumber,
+) -> Result<LargeNumber, IntcodeError> {
+    let mut mem = memory.clone();
+    mem[1] = noun;
+    mem[2] = verb;
+    run_intcode(mem).map(|m| m[0])
+}
+
+/// Parses the program, which needs cells for the noun and verb
+fn parse_memory(input: &str) -> Result<Memory, Box<dyn Error>> {
+    let memory = parse_program(input)?;
+    if memory.len() < 3 {
+        return Err(format!(
+            "program of {} cells has no room for a noun and verb",
+            memory.len()
+        )
+        .into());
+    }
+    Ok(memory)
+}
+
+/// Value of memory[0] that part two looks for
+const TARGET: LargeNumber = 19690720;
+
+/// Cells holding the noun and verb
+const NOUN: Var = Var::Cell(1);
+const VERB: Var = Var::Cell(2);
+
+#[derive(Clone, Debug, PartialEq)]
+enum SymbolicError {
+    Intcode(IntcodeError),
+    /// An opcode, jump target or written address depends on the noun or verb
+    Dynamic {
+        pc: usize,
+    },
+    StepLimit {
+        pc: usize,
+    },
+    /// The program branches on the noun or verb
+    Branches,
+    /// memory[0] was computed from a cell read through the noun or verb
+    Opaque,
+}
+
+impl fmt::Display for SymbolicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Intcode(e) => write!(f, "{}", e),
+            Self::Dynamic { pc } => {
+                write!(f, "the instruction at {} depends on the noun or verb", pc)
+            }
+            Self::StepLimit { pc } => write!(f, "gave up at {}, the program runs too long", pc),
+            Self::Branches => write!(f, "the program branches on the noun or verb"),
+            Self::Opaque => write!(
+                f,
+                "memory[0] depends on a cell addressed by the noun or verb"
+            ),
+        }
+    }
+}
+
+impl Error for SymbolicError {}
+
+/// Runs the program with the noun and verb left unknown, returning the
+/// expression left in memory[0]
+///
+/// Cells read through an unknown address become opaque, which is fine as
+/// long as they are overwritten before memory[0] depends on them.
+fn symbolic(memory: &Memory) -> Result<Expr, SymbolicError> {
+    let mut sym = Symbolic::new(memory.clone());
+    sym.with_variable(1)
+        .with_variable(2)
+        .with_opaque_reads(true);
+    let mut paths = sym.explore();
+    if paths.len() != 1 {
+        return Err(SymbolicError::Branches);
+    }
+    let path = paths.remove(0);
+    match path.end {
+        End::Halted => {}
+        End::Error(e) => return Err(SymbolicError::Intcode(e)),
+        End::Symbolic { pc } => return Err(SymbolicError::Dynamic { pc }),
+        End::StepLimit { pc } => return Err(SymbolicError::StepLimit { pc }),
+        End::Abandoned { .. } => return Err(SymbolicError::Branches),
+    }
+    let expr = path.mem.into_iter().next().unwrap_or(Expr::Const(0));
+    if expr.vars().iter().any(|v| matches!(v, Var::Opaque(_))) {
+        return Err(SymbolicError::Opaque);
+    }
+    Ok(expr)
+}
+
+/// A noun and verb in 0..=99 for which `expr` equals `target`, solved
+/// directly when `expr` is linear and searched for otherwise
+fn solve(expr: &Expr, target: LargeNumber) -> Option<(LargeNumber, LargeNumber)> {
+    let mut wanted = vec![Constraint::eq(expr.clone(), Expr::Const(target))];
+    for var in &[NOUN, VERB] {
+        let var = Expr::Var(*var);
+        wanted.push(Constraint::lt(var.clone(), Expr::Const(0)).negate());
+        wanted.push(Constraint::lt(var, Expr::Const(100)));
+    }
+    let model = match expr.linear().map(|_| check(&wanted)) {
+        Some(Check::Sat(model)) => model,
+        Some(Check::Unsat) => return None,
+        Some(Check::Unknown) | None => product(vec![(0..=99).collect(); 2])
+            .map(|nv| vec![(NOUN, nv[0]), (VERB, nv[1])].into_iter().collect())
+            .find(|model| expr.eval(model) == Some(target))?,
+    };
+    let value = |var| model.get(&var).copied().unwrap_or(0);
+    Some((value(NOUN), value(VERB)))
+}
+
+/// The closed form of memory[0] and the noun and verb that give 19690720
+pub fn explain_noun_verb(input: &str) -> Answer {
+    let memory = parse_memory(input)?;
+    let expr = symbolic(&memory)?;
+    let closed = expr.linear().map_or(expr.to_string(), |l| l.to_string());
+    let mut out = format!("memory[0] = {}\n", closed);
+    match solve(&expr, TARGET) {
+        Some((noun, verb)) => {
+            out += &format!("noun = {}, verb = {}: {}\n", noun, verb, 100 * noun + verb)
+        }
+        None => out += &format!("no noun and verb produce {}\n", TARGET),
+    }
+    Ok(out)
+}
+
+/// Tries every noun and verb, skipping those the program fails on
+fn search(memory: &Memory) -> Option<(LargeNumber, LargeNumber)> {
+    product(vec![(0..=99).collect(); 2])
+        .find(|nv| run_with(memory, nv[0], nv[1]) == Ok(TARGET))
+        .map(|nv| (nv[0], nv[1]))
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    fn day(&self) -> u8 {
+        2
+    }
+
+    fn title(&self) -> &'static str {
+        "1202 Program Alarm"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_memory(input)?))
+    }
+
+    /// Restores the gravity assist program to its 1202 program alarm state
+    fn part_one(&self, input: &Parsed) -> Answer {
+        Ok(run_with(parsed::<Memory>(input)?, 12, 2)?.to_string())
+    }
+
+    /// Solves for the noun and verb symbolically, searching when the
+    /// program is out of reach of `symbolic`
+    fn part_two(&self, input: &Parsed) -> Answer {
+        let memory = parsed::<Memory>(input)?;
+        let found = symbolic(memory)
+            .ok()
+            .and_then(|expr| solve(&expr, TARGET))
+            .filter(|&(noun, verb)| run_with(memory, noun, verb) == Ok(TARGET))
+            .or_else(|| search(memory));
+        match found {
+            Some((noun, verb)) => Ok((100 * noun + verb).to_string()),
+            None => Err(format!("no noun and verb produce {}", TARGET).into()),
+        }
+    }
+}
+
+#[test]
+fn test_short_program() {
+    for input in ["99", "1,0"] {
+        assert!(Day2.part(1, input).is_err());
+        assert!(Day2.part(2, input).is_err());
+    }
+}
+
+#[test]
+fn test_run_intcode() {
+    assert_eq!(run_intcode(vec![1, 0, 0, 0, 99]), Ok(vec![2, 0, 0, 0, 99]));
+    assert_eq!(run_intcode(vec![2, 3, 0, 3, 99]), Ok(vec![2, 3, 0, 6, 99]));
+    assert_eq!(
+        run_intcode(vec![2, 4, 4, 5, 99, 0]),
+        Ok(vec![2, 4, 4, 5, 99, 9801])
+    );
+    assert_eq!(
+        run_intcode(vec![1, 1, 1, 4, 99, 5, 6, 0, 99]),
+        Ok(vec![30, 1, 1, 4, 2, 5, 6, 0, 99])
+    );
+
+    // reads no input and stops looping programs
+    assert_eq!(
+        run_intcode(vec![3, 0, 99]),
+        Err(IntcodeError::InputExhausted { pc: 0 })
+    );
+    assert!(matches!(
+        run_intcode(vec![1105, 1, 0]),
+        Err(IntcodeError::LimitExceeded { pc: 0, .. })
+    ));
+    // a zero noun jumps to the verb, which loops at 0 for verb 0
+    let prog = vec![1106, 0, 0, 1001, 1, TARGET - 5, 0, 99];
+    assert_eq!(search(&prog), Some((5, 0)));
+}
+
+#[test]
+fn test_symbolic() {
+    // memory[3] = memory[noun] + memory[verb], dead once overwritten by
+    // memory[3] = noun + verb, then memory[0] = memory[3] * memory[3] + 5
+    let prog = vec![1, 0, 0, 3, 1, 1, 2, 3, 2, 3, 3, 0, 1, 0, 17, 0, 99, 5];
+    let expr = symbolic(&prog).unwrap();
+    assert_eq!(expr.to_string(), "(mem[1] + mem[2])*(mem[1] + mem[2]) + 5");
+    assert_eq!(solve(&expr, 405), Some((0, 20)));
+    assert_eq!(run_with(&prog, 0, 20), Ok(405));
+    assert_eq!(solve(&expr, 6), Some((0, 1)));
+    assert_eq!(solve(&expr, 4), None);
+
+    // memory[0] = 360000 * noun + verb - 250702
+    let prog = vec![
+        1, 0, 0, 3, 2, 1, 17, 0, 1, 0, 2, 0, 1, 0, 18, 0, 99, 360000, -250702,
+    ];
+    let expr = symbolic(&prog).unwrap();
+    assert_eq!(
+        expr.linear().unwrap().to_string(),
+        "360000*mem[1] + mem[2] - 250702"
+    );
+    let target = 360000 * 76 + 10 - 250702;
+    assert_eq!(solve(&expr, target), Some((76, 10)));
+    assert_eq!(run_with(&prog, 76, 10), Ok(target));
+
+    // the verb is copied into the destination of the third instruction
+    let prog = vec![1, 0, 0, 3, 1, 2, 13, 11, 1, 0, 0, 0, 99, 0];
+    assert_eq!(symbolic(&prog), Err(SymbolicError::Dynamic { pc: 8 }));
+    assert_eq!(symbolic(&vec![1, 0, 0, 0, 99]), Err(SymbolicError::Opaque));
+    // halts at 11 or 12, depending on noun + verb
+    let prog = vec![1, 0, 0, 3, 1, 1, 2, 13, 1005, 13, 12, 99, 99, 0];
+    assert_eq!(symbolic(&prog), Err(SymbolicError::Branches));
+}
@@ -2,18 +2,24 @@
 //
 // https://adventofcode.com/2019/day/3
 //
+use super::{parsed, Answer, Parsed, Solution};
+use crate::parse;
 use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
 
 type Point = (i32, i32);
 type TracePath = Vec<Point>;
 type Direction = (char, usize);
 type Directions = Vec<Direction>;
 
-
 fn trace_path(directions: Directions) -> TracePath {
-    struct Pos { pub x: i32, pub y: i32, }
+    struct Pos {
+        pub x: i32,
+        pub y: i32,
+    }
     let mut trace = Vec::new();
-    let mut pos = Pos{x:0, y:0};
+    let mut pos = Pos { x: 0, y: 0 };
     for (d, steps) in directions {
         match d {
             'R' => {
@@ -47,67 +53,87 @@ fn trace_path(directions: Directions) -> TracePath {
     trace
 }
 
-fn steps_to_intersection(tp: &TracePath, inter: &Point) -> Option<i32> {
+fn steps_to_intersection(tp: &[Point], inter: &Point) -> Option<i32> {
     for (idx, point) in tp.iter().enumerate() {
         if point == inter {
-            return Some(idx as i32 + 1 as i32);
+            return Some(idx as i32 + 1);
         }
     }
     None
 }
 
-fn get_intersections(p1: &Vec<(i32, i32)>, p2: &Vec<(i32, i32)>) -> Vec<Point> {
+fn get_intersections(p1: &[Point], p2: &[Point]) -> Vec<Point> {
     let h1: HashSet<(i32, i32)> = p1.iter().copied().collect();
     let h2: HashSet<(i32, i32)> = p2.iter().copied().collect();
     h1.intersection(&h2).copied().collect::<Vec<_>>()
 }
 
-fn mdist(a:  Point, b: Point) -> i32 {
-    ((a.0 - b.0).abs() + (a.1 - b.1).abs())
+fn mdist(a: Point, b: Point) -> i32 {
+    (a.0 - b.0).abs() + (a.1 - b.1).abs()
 }
 
-fn manhattan_dist(p1: Directions, p2: Directions) -> i32 {
+/// `None` when the wires never cross
+fn manhattan_dist(p1: Directions, p2: Directions) -> Option<i32> {
     get_intersections(&trace_path(p1), &trace_path(p2))
         .iter()
         .map(|x| mdist(*x, (0, 0)))
         .min()
-        .unwrap()
 }
 
-fn minimal_steps(p1: Directions, p2: Directions) -> i32 {
+/// `None` when the wires never cross
+fn minimal_steps(p1: Directions, p2: Directions) -> Option<i32> {
     let tp1 = trace_path(p1);
     let tp2 = trace_path(p2);
     let intersections = get_intersections(&tp1, &tp2);
     let mut steps = vec![];
     for x in intersections {
-        steps.push(
-            steps_to_intersection(&tp1, &x).unwrap() + steps_to_intersection(&tp2, &x).unwrap(),
-        );
+        steps.push(steps_to_intersection(&tp1, &x)? + steps_to_intersection(&tp2, &x)?);
     }
 
-    *steps.iter().min().unwrap()
+    steps.into_iter().min()
 }
 
+#[cfg(test)]
 fn parse_directions(d: Vec<&str>) -> Vec<Direction> {
     d.iter()
-        .map(|x| (x.chars().nth(0).unwrap(), x[1..].parse().unwrap()))
+        .map(|x| (x.chars().next().unwrap(), x[1..].parse().unwrap()))
         .collect()
 }
 
-fn main() {
-    let paths: Vec<Directions> = include_str!("input3.txt")
-        .lines()
-        .map(|l| l.split(',').collect())
-        .map(|x| parse_directions(x))
-        .collect();
-    println!(
-        "Part One: {:?}",
-        manhattan_dist(paths[0].clone(), paths[1].clone())
-    );
-    println!(
-        "Part Two: {:?}",
-        minimal_steps(paths[0].clone(), paths[1].clone())
-    );
+/// The two wires, one per line
+fn parse_paths(input: &str) -> Result<(Directions, Directions), Box<dyn Error>> {
+    match <[Directions; 2]>::try_from(parse::directions(input)?) {
+        Ok([p1, p2]) => Ok((p1, p2)),
+        Err(paths) => Err(format!("expected 2 wires, found {}", paths.len()).into()),
+    }
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    fn day(&self) -> u8 {
+        3
+    }
+
+    fn title(&self) -> &'static str {
+        "Crossed Wires"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_paths(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        let (p1, p2) = parsed::<(Directions, Directions)>(input)?.clone();
+        let dist = manhattan_dist(p1, p2).ok_or("the wires never cross")?;
+        Ok(dist.to_string())
+    }
+
+    fn part_two(&self, input: &Parsed) -> Answer {
+        let (p1, p2) = parsed::<(Directions, Directions)>(input)?.clone();
+        let steps = minimal_steps(p1, p2).ok_or("the wires never cross")?;
+        Ok(steps.to_string())
+    }
 }
 
 #[test]
@@ -122,8 +148,9 @@ fn test_manhattan_dist() {
     let p4 = parse_directions(vec![
         "U98", "R91", "D20", "R16", "D67", "R40", "U7", "R15", "U6", "R7",
     ]);
-    assert_eq!(159, manhattan_dist(p1, p2));
-    assert_eq!(135, manhattan_dist(p3, p4));
+    assert_eq!(Some(159), manhattan_dist(p1, p2));
+    assert_eq!(Some(135), manhattan_dist(p3, p4));
+    assert!(Day3.part(1, "R8\nU5").is_err());
 }
 
 #[test]
@@ -138,6 +165,7 @@ fn test_minimal_steps() {
     let p4 = parse_directions(vec![
         "U98", "R91", "D20", "R16", "D67", "R40", "U7", "R15", "U6", "R7",
     ]);
-    assert_eq!(610, minimal_steps(p1, p2));
-    assert_eq!(410, minimal_steps(p3, p4));
+    assert_eq!(Some(610), minimal_steps(p1, p2));
+    assert_eq!(Some(410), minimal_steps(p3, p4));
+    assert!(Day3.part(2, "R8\nU5").is_err());
 }
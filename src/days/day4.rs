@@ -0,0 +1,111 @@
+// --- Day 4: Secure Container ---
+//
+// https://adventofcode.com/2019/day/4
+//
+use super::{parsed, Answer, Parsed, Solution};
+use crate::parse;
+use std::error::Error;
+use std::ops::RangeInclusive;
+
+fn check_double(mut x: i32) -> bool {
+    let mut prev = x % 10;
+    x /= 10;
+    while x >= 1 {
+        let d = x % 10;
+        if d == prev {
+            return true;
+        }
+        prev = d;
+        x /= 10;
+    }
+    false
+}
+
+fn check_distinct_double(x: i32) -> bool {
+    // when all fails, quick and dirty works
+    check_double_substr(&format!("{}", x))
+}
+
+fn check_double_substr(s: &str) -> bool {
+    // use run-length encoding
+    let chars: Vec<char> = s.chars().collect();
+    let mut counts = std::collections::HashSet::new();
+    let mut count = 1;
+    let mut prev = &chars[0];
+    for c in &chars[1..] {
+        if c != prev {
+            counts.insert(count);
+            count = 1;
+            prev = c;
+        } else {
+            count += 1;
+        }
+    }
+    counts.insert(count);
+    counts.contains(&2)
+}
+
+fn never_decrease(mut x: i32) -> bool {
+    let mut prev = x % 10;
+    x /= 10;
+    while x >= 1 {
+        let d = x % 10;
+        if d > prev {
+            return false;
+        }
+        prev = d;
+        x /= 10;
+    }
+    true
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    fn day(&self) -> u8 {
+        4
+    }
+
+    fn title(&self) -> &'static str {
+        "Secure Container"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse::range::<i32>(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<RangeInclusive<i32>>(input)?
+            .clone()
+            .filter(|x| check_double(*x) && never_decrease(*x))
+            .count()
+            .to_string())
+    }
+
+    fn part_two(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<RangeInclusive<i32>>(input)?
+            .clone()
+            .filter(|x| check_distinct_double(*x) && never_decrease(*x))
+            .count()
+            .to_string())
+    }
+}
+
+#[test]
+fn tests() {
+    assert!(check_double(111111));
+    assert!(check_double(223450));
+    assert!(never_decrease(111111));
+    assert!(!never_decrease(223450));
+    assert!(never_decrease(123789));
+    assert!(!check_double(123789));
+    assert!(check_distinct_double(112233));
+    assert!(!check_distinct_double(123444));
+    assert!(!check_distinct_double(567777));
+    assert!(check_distinct_double(111122));
+}
+
+#[test]
+fn test_check_double_substr() {
+    assert!(check_double_substr("22"));
+}
@@ -0,0 +1,124 @@
+// --- Day 5: Sunny with a Chance of Asteroids ---
+//
+// https://adventofcode.com/2019/day/5
+//
+//
+
+use super::{parsed, Answer, Parsed, Solution};
+use crate::intcode::{parse_program, IntCodeComputer, LargeNumber, Program, QueueIo};
+use std::error::Error;
+
+/// Runs the diagnostic program for a system, returning its diagnostic code
+fn diagnose(prog: &Program, system: LargeNumber) -> Answer {
+    let mut computer = IntCodeComputer::with_io(prog.clone(), QueueIo::new(vec![system]));
+    computer.run()?;
+    let mut outputs = computer.io.output;
+    let code = outputs.pop_back().ok_or("no diagnostic code")?;
+    // every test before the code reports how far off it was
+    if let Some(pos) = outputs.iter().position(|v| *v != 0) {
+        return Err(format!("diagnostic test {} failed: {}", pos, outputs[pos]).into());
+    }
+    Ok(code.to_string())
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    fn day(&self) -> u8 {
+        5
+    }
+
+    fn title(&self) -> &'static str {
+        "Sunny with a Chance of Asteroids"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_program(input)?))
+    }
+
+    /// The air conditioner unit
+    fn part_one(&self, input: &Parsed) -> Answer {
+        diagnose(parsed(input)?, 1)
+    }
+
+    /// The thermal radiator controller
+    fn part_two(&self, input: &Parsed) -> Answer {
+        diagnose(parsed(input)?, 5)
+    }
+}
+
+#[test]
+fn test_intcode_computer() {
+    let values = vec![
+        (vec![1, 0, 0, 0, 99], vec![2, 0, 0, 0, 99]),
+        (vec![2, 3, 0, 3, 99], vec![2, 3, 0, 6, 99]),
+        (vec![2, 4, 4, 5, 99, 0], vec![2, 4, 4, 5, 99, 9801]),
+        (
+            vec![1, 1, 1, 4, 99, 5, 6, 0, 99],
+            vec![30, 1, 1, 4, 2, 5, 6, 0, 99],
+        ),
+        (vec![1002, 4, 3, 4, 33], vec![1002, 4, 3, 4, 99]),
+    ];
+    for (inp, out) in values {
+        let mut c = IntCodeComputer::new(inp);
+        c.run().unwrap();
+        assert_eq!(c.mem, out);
+    }
+}
+
+#[test]
+fn test_checking8() {
+    let p = vec![
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    let mut c = IntCodeComputer::with_io(p, crate::intcode::IterIo::new(std::iter::repeat(9)));
+    c.run().unwrap();
+    assert_eq!(c.io.outputs, vec![1001]);
+}
+
+#[test]
+fn test_instruction_decoding() {
+    use crate::intcode::{AddressingMode, Instruction, Opcode};
+
+    let inst = Instruction::decode(104, 0).unwrap();
+    assert_eq!(inst.opcode, Opcode::LOAD);
+    assert_eq!(inst.pm1, AddressingMode::Immediate);
+    assert_eq!(inst.pm2, AddressingMode::Position);
+    assert_eq!(inst.pm3, AddressingMode::Position);
+}
+
+#[test]
+fn test_symbolic_system_ids() {
+    use crate::intcode::{Check, Constraint, End, Expr, Symbolic, Var};
+
+    // which system IDs pass every test and print a given diagnostic code;
+    // the ID is added to an opcode, so many of them run the same tests
+    let src = include_str!("../../inputs/input5.txt");
+    let prog = parse_program(src).unwrap();
+    let paths = Symbolic::new(prog.clone()).explore();
+    let id = Expr::Var(Var::Input(0));
+    for (code, expected) in [(8332629, 1), (8805067, 5)] {
+        let ids = paths
+            .iter()
+            .filter(|p| p.end == End::Halted && !p.outputs.is_empty())
+            .filter_map(|p| {
+                let (last, tests) = p.outputs.split_last().unwrap();
+                let mut wanted = tests
+                    .iter()
+                    .map(|t| Constraint::eq(t.clone(), Expr::Const(0)))
+                    .collect::<Vec<_>>();
+                wanted.push(Constraint::eq(last.clone(), Expr::Const(code)));
+                match p.solve(&wanted) {
+                    Check::Sat(model) => id.eval(&model),
+                    _ => None,
+                }
+            })
+            .collect::<Vec<_>>();
+        assert!(ids.contains(&expected));
+        for id in ids {
+            assert_eq!(diagnose(&prog, id).unwrap(), code.to_string());
+        }
+    }
+}
@@ -4,7 +4,10 @@
 //
 //
 
+use super::{parsed, Answer, Parsed, Solution};
+use crate::parse;
 use std::collections::HashMap;
+use std::error::Error;
 
 type ObjectID = usize;
 
@@ -37,14 +40,14 @@ struct OrbitalMap {
 }
 
 impl OrbitalMap {
-    fn from(data: Vec<&str>) -> OrbitalMap {
-        let sats_obj_map: HashMap<&str, &str> = data
-            .iter()
-            .map(|x| {
-                let p = x.splitn(2, ')').collect::<Vec<_>>();
-                (p[1], p[0])
-            })
-            .collect();
+    fn parse(input: &str) -> Result<OrbitalMap, Box<dyn Error>> {
+        OrbitalMap::from(parse::edges(input)?).ok_or_else(|| "no COM in the map".into())
+    }
+
+    /// `None` when nothing orbits the centre of mass
+    fn from(edges: Vec<(&str, &str)>) -> Option<OrbitalMap> {
+        let sats_obj_map: HashMap<&str, &str> =
+            edges.into_iter().map(|(obj, sat)| (sat, obj)).collect();
 
         let mut obj_map = HashMap::new();
         let mut objs = vec![];
@@ -73,10 +76,10 @@ impl OrbitalMap {
             objs[objid].add_sat(satid, sat);
         }
 
-        OrbitalMap {
-            centre: *obj_map.get("COM").unwrap(),
+        Some(OrbitalMap {
+            centre: *obj_map.get("COM")?,
             objects: objs,
-        }
+        })
     }
 
     /// Returns reversed DFS path from target's parent to the start
@@ -114,7 +117,9 @@ impl OrbitalMap {
     /// A crude LCA computation by finding the first common
     /// node on the reversed DFS paths of the two positions
     /// from the root (centre of mass).
-    fn orbital_transfers(&self, to: &str, frm: &str) -> usize {
+    ///
+    /// `None` when either object is missing from the map.
+    fn orbital_transfers(&self, to: &str, frm: &str) -> Option<usize> {
         let path1 = self.search(to, self.centre);
         let path2 = self.search(frm, self.centre);
         let mut cross = None;
@@ -124,7 +129,7 @@ impl OrbitalMap {
                 break;
             }
         }
-        let cross = cross.unwrap();
+        let cross = cross?;
         let mut count = 0;
         for p in &path1 {
             if p == cross {
@@ -140,7 +145,7 @@ impl OrbitalMap {
                 count += 1;
             }
         }
-        count
+        Some(count)
     }
 
     fn total_orbits(&self) -> usize {
@@ -156,17 +161,40 @@ impl OrbitalMap {
         count
     }
 }
-fn main() {
-    let map = OrbitalMap::from(include_str!("input6.txt").lines().collect());
-    println!("total orbits: {}", map.total_orbits());
-    println!("orbital transfers: {}", map.orbital_transfers("YOU", "SAN"));
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    fn day(&self) -> u8 {
+        6
+    }
+
+    fn title(&self) -> &'static str {
+        "Universal Orbit Map"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(OrbitalMap::parse(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<OrbitalMap>(input)?.total_orbits().to_string())
+    }
+
+    fn part_two(&self, input: &Parsed) -> Answer {
+        let transfers = parsed::<OrbitalMap>(input)?
+            .orbital_transfers("YOU", "SAN")
+            .ok_or("YOU or SAN is missing from the map")?;
+        Ok(transfers.to_string())
+    }
 }
 
 #[test]
 fn test_orbital_map() {
-    let map = OrbitalMap::from(vec![
-        "COM)B", "B)C", "C)D", "D)E", "E)F", "B)G", "G)H", "D)I", "E)J", "J)K", "K)L",
-    ]);
-    println!("{:#?}", map.orbital_transfers("I", "L"));
+    let map =
+        OrbitalMap::parse("COM)B\nB)C\nC)D\nD)E\nE)F\nB)G\nG)H\nD)I\nE)J\nJ)K\nK)L\n").unwrap();
+    assert_eq!(map.orbital_transfers("I", "L"), Some(3));
     assert_eq!(map.total_orbits(), 42);
+    assert!(OrbitalMap::parse("A)B\n").is_err());
+    assert!(Day6.part(2, "COM)B").is_err());
 }
@@ -0,0 +1,578 @@
+// --- Day 7: Amplification Circuit ---
+//
+// https://adventofcode.com/2019/day/7
+//
+use super::{parsed, Answer, Parsed, Solution};
+use crate::combinatorics::permutations;
+#[cfg(test)]
+use crate::intcode::FEEDBACK_LOOP;
+use crate::intcode::{
+    parse_program, Delivery, IntCodeComputer, IntcodeError, LargeNumber, Limit, Limits,
+    MachineStatus, Network, NetworkReport, Program, QueueIo, StepOutcome, Topology,
+};
+use crate::search::{self, Objective};
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::sync::Mutex;
+use std::time::Duration;
+
+type Sequence = Vec<LargeNumber>;
+
+/// Instructions an amplifier may run before it is taken to be looping
+const STEP_LIMIT: usize = 1_000_000;
+
+/// Time an amplifier may run for before it is taken to be stalled
+const TIME_LIMIT: Duration = Duration::from_secs(5);
+
+/// Applied to every amplifier unless overridden
+const LIMITS: Limits = Limits {
+    steps: Some(STEP_LIMIT),
+    memory: None,
+    time: Some(TIME_LIMIT),
+};
+
+#[derive(Debug, PartialEq)]
+enum ControllerError {
+    Intcode {
+        amp: usize,
+        error: IntcodeError,
+    },
+    /// Every running amplifier waits on an empty inbox
+    Deadlock {
+        stuck: Vec<(usize, usize)>,
+    },
+    /// An amplifier ran past one of its limits, leaving these unfinished
+    Stalled {
+        limit: Limit,
+        stuck: Vec<(usize, usize)>,
+    },
+    /// The last amplifier halted without an output
+    NoSignal,
+}
+
+fn amp_name(amp: usize) -> char {
+    (b'A' + amp as u8) as char
+}
+
+impl fmt::Display for ControllerError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let stuck = match self {
+            Self::Intcode { amp, error } => {
+                return write!(f, "amplifier {}: {}", amp_name(*amp), error)
+            }
+            Self::Deadlock { stuck } => {
+                write!(f, "deadlock, blocked on empty inboxes:")?;
+                stuck
+            }
+            Self::Stalled { limit, stuck } => {
+                write!(f, "{} exceeded, stalled:", limit)?;
+                stuck
+            }
+            Self::NoSignal => return write!(f, "the last amplifier sent no signal"),
+        };
+        for (amp, pc) in stuck {
+            write!(f, " {} (pc {})", amp_name(*amp), pc)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for ControllerError {}
+
+/// Every signal sent down the channels of the chain, as the network
+/// delivered them. The controller's input is sent from `None`
+#[derive(Clone, Debug, PartialEq)]
+struct SignalTrace {
+    amps: usize,
+    signals: Vec<Delivery>,
+}
+
+impl SignalTrace {
+    fn sender(from: Option<usize>) -> String {
+        from.map_or("in".to_string(), |amp| amp_name(amp).to_string())
+    }
+
+    /// One row per signal, in the order they were sent
+    fn table(&self) -> String {
+        let mut out = format!(
+            "{:>5} {:>5} {:>4} {:>3} {:>12}\n",
+            "seq", "round", "from", "to", "value"
+        );
+        for s in &self.signals {
+            out += &format!(
+                "{:>5} {:>5} {:>4} {:>3} {:>12}\n",
+                s.seq,
+                s.round,
+                SignalTrace::sender(s.from),
+                amp_name(s.to),
+                s.value
+            );
+        }
+        out
+    }
+
+    /// Graphviz digraph with one edge per channel, labelled with its signals
+    fn dot(&self) -> String {
+        let mut out = String::from("digraph amplifiers {\n    rankdir=LR;\n");
+        let mut channels = vec![None];
+        channels.extend((0..self.amps).map(Some));
+        for from in channels {
+            let labels = self
+                .signals
+                .iter()
+                .filter(|s| s.from == from)
+                .map(|s| format!("#{} round {}: {}", s.seq, s.round, s.value))
+                .collect::<Vec<_>>();
+            if labels.is_empty() {
+                continue;
+            }
+            let to = self.signals.iter().find(|s| s.from == from).unwrap().to;
+            out += &format!(
+                "    {} -> {} [label=\"{}\"];\n",
+                SignalTrace::sender(from),
+                amp_name(to),
+                labels.join("\\n")
+            );
+        }
+        out += "}\n";
+        out
+    }
+}
+
+/// Amplifiers wired into a feedback loop on a `Network`
+struct AmpController {
+    net: Network,
+    amps: usize,
+}
+
+impl AmpController {
+    /// Chains the amplifiers into a ring, each input primed with the
+    /// amplifier's phase setting and limited by `LIMITS`
+    fn new(prog: Program, seq: Sequence) -> AmpController {
+        let amps = seq
+            .iter()
+            .map(|phase| {
+                let mut amp = IntCodeComputer::with_io(prog.clone(), QueueIo::new(vec![*phase]));
+                amp.with_limits(LIMITS);
+                amp
+            })
+            .collect::<Vec<_>>();
+        let n = amps.len();
+        // a ring only connects machines that exist
+        let net = Network::with_topology(amps, Topology::Ring).unwrap();
+        AmpController { net, amps: n }
+    }
+
+    fn with_limits(&mut self, limits: Limits) -> &mut Self {
+        for id in 0..self.amps {
+            // every id below `amps` is a machine of the ring
+            self.net.machine_mut(id).unwrap().with_limits(limits);
+        }
+        self
+    }
+
+    fn run(self, inp: LargeNumber) -> Result<LargeNumber, ControllerError> {
+        self.run_traced(inp).0
+    }
+
+    /// Also returns every signal sent between the amplifiers
+    fn run_traced(
+        mut self,
+        inp: LargeNumber,
+    ) -> (Result<LargeNumber, ControllerError>, SignalTrace) {
+        // with no amplifiers the input goes nowhere
+        let _ = self.net.send(0, inp);
+        let report = self.net.run();
+        let trace = SignalTrace {
+            amps: self.amps,
+            signals: self.net.deliveries,
+        };
+        (AmpController::signal(&report), trace)
+    }
+
+    /// The last amplifier's final output
+    fn signal(report: &NetworkReport) -> Result<LargeNumber, ControllerError> {
+        for (amp, machine) in report.machines.iter().enumerate() {
+            match &machine.status {
+                MachineStatus::Failed(IntcodeError::LimitExceeded { limit, .. }) => {
+                    let stuck = report
+                        .machines
+                        .iter()
+                        .enumerate()
+                        .filter(|(_, m)| m.status != MachineStatus::Halted)
+                        .map(|(amp, m)| (amp, m.pc))
+                        .collect();
+                    let limit = *limit;
+                    return Err(ControllerError::Stalled { limit, stuck });
+                }
+                MachineStatus::Failed(error) => {
+                    let error = error.clone();
+                    return Err(ControllerError::Intcode { amp, error });
+                }
+                _ => {}
+            }
+        }
+        if report.deadlocked {
+            let stuck = report
+                .waiting()
+                .into_iter()
+                .map(|amp| (amp, report.machines[amp].pc))
+                .collect();
+            return Err(ControllerError::Deadlock { stuck });
+        }
+        let last = report.machines.last();
+        last.and_then(|m| m.outputs.last().copied())
+            .ok_or(ControllerError::NoSignal)
+    }
+}
+
+/// Searches phase sequences for the best final signal
+///
+/// Amplifiers that halt after a single output are run on their own and
+/// memoised by phase and input signal, so sequences sharing a prefix share
+/// the work. Feedback loops fall back to a full `AmpController` run.
+struct Optimiser {
+    prog: Program,
+    workers: usize,
+    /// Applied to every amplifier it runs
+    limits: Limits,
+    passes: Mutex<HashMap<(LargeNumber, LargeNumber), Option<LargeNumber>>>,
+    loops: Mutex<HashMap<Sequence, LargeNumber>>,
+}
+
+impl Optimiser {
+    fn new(prog: Program) -> Optimiser {
+        Optimiser {
+            prog,
+            workers: search::workers(),
+            limits: LIMITS,
+            passes: Mutex::new(HashMap::new()),
+            loops: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Output of a single amplifier, if it halts right after producing it
+    fn pass(
+        &self,
+        amp: usize,
+        phase: LargeNumber,
+        inp: LargeNumber,
+    ) -> Result<Option<LargeNumber>, ControllerError> {
+        if let Some(out) = self.passes.lock().unwrap().get(&(phase, inp)) {
+            return Ok(*out);
+        }
+        let mut c = IntCodeComputer::with_io(self.prog.clone(), QueueIo::new(vec![phase, inp]));
+        c.with_limits(self.limits);
+        let out = match c.run_until(|_| false) {
+            Ok(StepOutcome::Halted) if c.io.output.len() == 1 => c.io.output.pop_front(),
+            Ok(_) => None,
+            Err(IntcodeError::LimitExceeded { limit, pc }) => {
+                let stuck = vec![(amp, pc)];
+                return Err(ControllerError::Stalled { limit, stuck });
+            }
+            Err(error) => return Err(ControllerError::Intcode { amp, error }),
+        };
+        self.passes.lock().unwrap().insert((phase, inp), out);
+        Ok(out)
+    }
+
+    fn signal(&self, seq: &[LargeNumber]) -> Result<LargeNumber, ControllerError> {
+        let mut signal = 0;
+        for (amp, phase) in seq.iter().enumerate() {
+            match self.pass(amp, *phase, signal)? {
+                Some(out) => signal = out,
+                None => return self.feedback(seq),
+            }
+        }
+        Ok(signal)
+    }
+
+    fn feedback(&self, seq: &[LargeNumber]) -> Result<LargeNumber, ControllerError> {
+        if let Some(signal) = self.loops.lock().unwrap().get(seq) {
+            return Ok(*signal);
+        }
+        let mut amc = AmpController::new(self.prog.clone(), seq.to_vec());
+        amc.with_limits(self.limits);
+        let signal = amc.run(0)?;
+        self.loops.lock().unwrap().insert(seq.to_vec(), signal);
+        Ok(signal)
+    }
+
+    /// Best candidate and its signal, the earliest one on ties
+    fn search<I>(
+        &self,
+        candidates: I,
+        objective: Objective,
+    ) -> Result<Option<(Sequence, LargeNumber)>, ControllerError>
+    where
+        I: IntoIterator<Item = Sequence>,
+        I::IntoIter: Send,
+    {
+        search::best(candidates, objective, self.workers, |seq| self.signal(seq))
+    }
+}
+
+fn best_sequence(
+    prog: Program,
+    phases: Sequence,
+) -> Result<(Sequence, LargeNumber), ControllerError> {
+    let opt = Optimiser::new(prog);
+    // permutations always yield at least one sequence
+    Ok(opt.search(permutations(phases), Objective::Max)?.unwrap())
+}
+
+pub struct Day7;
+
+impl Solution for Day7 {
+    fn day(&self) -> u8 {
+        7
+    }
+
+    fn title(&self) -> &'static str {
+        "Amplification Circuit"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_program(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        let prog = parsed::<Program>(input)?.clone();
+        Ok(best_sequence(prog, vec![0, 1, 2, 3, 4])?.1.to_string())
+    }
+
+    /// Amplifiers wired into a feedback loop
+    fn part_two(&self, input: &Parsed) -> Answer {
+        let prog = parsed::<Program>(input)?.clone();
+        Ok(best_sequence(prog, vec![5, 6, 7, 8, 9])?.1.to_string())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum SignalFormat {
+    Table,
+    Dot,
+}
+
+/// Signals of the best phase sequence of a part, sent between the amplifiers
+pub fn render_signals(input: &str, part: u8, format: SignalFormat) -> Answer {
+    let phases = match part {
+        1 => vec![0, 1, 2, 3, 4],
+        2 => vec![5, 6, 7, 8, 9],
+        _ => return Err(format!("day 7 has no part {}", part).into()),
+    };
+    let prog = parse_program(input)?;
+    let (seq, _) = best_sequence(prog.clone(), phases)?;
+    let (res, trace) = AmpController::new(prog, seq).run_traced(0);
+    res?;
+    Ok(match format {
+        SignalFormat::Table => trace.table(),
+        SignalFormat::Dot => trace.dot(),
+    })
+}
+
+#[test]
+fn test_amp_controller() {
+    let progs = vec![
+        (
+            vec![
+                3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+            ],
+            vec![4, 3, 2, 1, 0],
+            43210,
+        ),
+        (
+            vec![
+                3, 23, 3, 24, 1002, 24, 10, 24, 1002, 23, -1, 23, 101, 5, 23, 23, 1, 24, 23, 23, 4,
+                23, 99, 0, 0,
+            ],
+            vec![0, 1, 2, 3, 4],
+            54321,
+        ),
+        (
+            vec![
+                3, 31, 3, 32, 1002, 32, 10, 32, 1001, 31, -2, 31, 1007, 31, 0, 33, 1002, 33, 7, 33,
+                1, 33, 31, 31, 1, 32, 31, 31, 4, 31, 99, 0, 0, 0,
+            ],
+            vec![1, 0, 4, 3, 2],
+            65210,
+        ),
+    ];
+    for (prog, seq, res) in progs {
+        assert_eq!(Ok(res), AmpController::new(prog, seq).run(0));
+    }
+}
+
+#[test]
+fn test_controller() {
+    let progs = vec![
+        (FEEDBACK_LOOP.to_vec(), vec![9, 8, 7, 6, 5], 139629729),
+        (
+            vec![
+                3, 52, 1001, 52, -5, 52, 3, 53, 1, 52, 56, 54, 1007, 54, 5, 55, 1005, 55, 26, 1001,
+                54, -5, 54, 1105, 1, 12, 1, 53, 54, 53, 1008, 54, 0, 55, 1001, 55, 1, 55, 2, 53,
+                55, 53, 4, 53, 1001, 56, -1, 56, 1005, 56, 6, 99, 0, 0, 0, 0, 10,
+            ],
+            vec![9, 7, 8, 5, 6],
+            18216,
+        ),
+    ];
+    for (prog, seq, res) in progs {
+        let amc = AmpController::new(prog, seq);
+        assert_eq!(Ok(res), amc.run(0));
+    }
+}
+
+#[test]
+fn test_deadlock() {
+    // needs a third input that never comes
+    let prog = vec![3, 11, 3, 11, 3, 11, 4, 11, 99, 0, 0, 0];
+    let err = AmpController::new(prog, vec![0, 1, 2, 3, 4]).run(0);
+    assert_eq!(
+        err,
+        Err(ControllerError::Deadlock {
+            stuck: vec![(0, 4), (1, 2), (2, 2), (3, 2), (4, 2)]
+        })
+    );
+    assert_eq!(
+        err.unwrap_err().to_string(),
+        "deadlock, blocked on empty inboxes: A (pc 4) B (pc 2) C (pc 2) D (pc 2) E (pc 2)"
+    );
+}
+
+#[test]
+fn test_no_signal() {
+    let amc = AmpController::new(vec![99], vec![0, 1]);
+    assert_eq!(amc.run(0), Err(ControllerError::NoSignal));
+    // the signal is what the last amplifier sent, not its unread phase
+    let amc = AmpController::new(vec![104, 5, 99], vec![0, 1]);
+    assert_eq!(amc.run(0), Ok(5));
+
+    // A halts on phase 0 with the input unread, while B waits on A
+    let prog = vec![3, 9, 1005, 9, 6, 99, 3, 9, 99, 0];
+    let amc = AmpController::new(prog, vec![0, 1]);
+    assert_eq!(
+        amc.run(0),
+        Err(ControllerError::Deadlock {
+            stuck: vec![(1, 6)]
+        })
+    );
+}
+
+#[test]
+fn test_step_limit() {
+    // every amplifier spins without reading its phase, stopped by default
+    let err = AmpController::new(vec![1105, 1, 0], vec![0, 1]).run(0);
+    assert_eq!(
+        err,
+        Err(ControllerError::Stalled {
+            limit: Limit::Steps(STEP_LIMIT),
+            stuck: vec![(0, 0), (1, 0)]
+        })
+    );
+
+    // the first amplifier spins forever on phase 0, starving the rest
+    let prog = vec![
+        3, 15, 1008, 15, 0, 16, 1005, 16, 6, 3, 15, 4, 15, 99, 0, 0, 0,
+    ];
+    let mut amc = AmpController::new(prog.clone(), vec![0, 1]);
+    amc.with_limits(Limits {
+        steps: Some(1000),
+        ..Limits::default()
+    });
+    let err = amc.run(0).unwrap_err();
+    assert_eq!(
+        err.to_string(),
+        "step limit of 1000 exceeded, stalled: A (pc 6) B (pc 9)"
+    );
+
+    let mut amc = AmpController::new(prog, vec![0, 1]);
+    let time = Duration::from_millis(50);
+    amc.with_limits(Limits {
+        time: Some(time),
+        ..Limits::default()
+    });
+    // A uses up the time, so B is stopped before its first instruction
+    assert_eq!(
+        amc.run(0),
+        Err(ControllerError::Stalled {
+            limit: Limit::Time(time),
+            stuck: vec![(0, 6), (1, 0)]
+        })
+    );
+}
+
+#[test]
+fn test_optimiser() {
+    let prog = vec![
+        3, 15, 3, 16, 1002, 16, 10, 16, 1, 16, 15, 15, 4, 15, 99, 0, 0,
+    ];
+    let opt = Optimiser::new(prog);
+    assert_eq!(
+        opt.search(permutations(vec![0, 1, 2, 3, 4]), Objective::Max),
+        Ok(Some((vec![4, 3, 2, 1, 0], 43210)))
+    );
+    // at most one run per distinct prefix (5 + 20 + 60 + 120 + 120) rather than 600
+    assert!(opt.passes.lock().unwrap().len() <= 325);
+    assert_eq!(
+        opt.search(permutations(vec![0, 1, 2, 3, 4]), Objective::Min),
+        Ok(Some((vec![0, 1, 2, 3, 4], 1234)))
+    );
+    assert_eq!(opt.search(vec![], Objective::Max), Ok(None));
+
+    // feedback amplifiers never halt after one output, so every sequence
+    // runs as a loop, once
+    let opt = Optimiser::new(FEEDBACK_LOOP.to_vec());
+    let best = opt.search(permutations(vec![5, 6, 7, 8, 9]), Objective::Max);
+    assert_eq!(
+        best.map(|b| b.map(|(seq, _)| seq)),
+        Ok(Some(vec![9, 8, 7, 6, 5]))
+    );
+    assert!(opt.passes.lock().unwrap().values().all(Option::is_none));
+    assert_eq!(opt.loops.lock().unwrap().len(), 120);
+    opt.search(permutations(vec![5, 6, 7, 8, 9]), Objective::Max)
+        .unwrap();
+    assert_eq!(opt.loops.lock().unwrap().len(), 120);
+}
+
+#[test]
+fn test_signal_trace() {
+    let (res, trace) =
+        AmpController::new(FEEDBACK_LOOP.to_vec(), vec![9, 8, 7, 6, 5]).run_traced(0);
+    // the input, then five passes round the loop of five amplifiers
+    assert_eq!(trace.signals.len(), 26);
+    let last = trace.signals.last().unwrap();
+    assert_eq!((last.round, last.from, last.to), (24, Some(4), 0));
+    assert_eq!(Ok(last.value), res);
+
+    assert!(trace
+        .table()
+        .starts_with("  seq round from  to        value\n    0     0   in   A            0\n"));
+    let dot = trace.dot();
+    assert!(dot.starts_with(
+        "digraph amplifiers {\n    rankdir=LR;\n    in -> A [label=\"#0 round 0: 0\"];\n"
+    ));
+    assert!(dot.contains(&format!("#25 round 24: {}\"];", last.value)));
+    assert_eq!(dot.matches(" -> ").count(), 6);
+
+    // each amplifier sends its input and then its phase in the same round
+    let prog = vec![3, 100, 3, 101, 4, 101, 4, 100, 99];
+    let (res, trace) = AmpController::new(prog, vec![7, 8]).run_traced(0);
+    assert_eq!(res, Ok(8));
+    let sent = trace
+        .signals
+        .iter()
+        .map(|s| (s.seq, s.round, s.from, s.to, s.value))
+        .collect::<Vec<_>>();
+    assert_eq!(
+        sent,
+        vec![
+            (0, 0, None, 0, 0),
+            (1, 0, Some(0), 1, 0),
+            (2, 0, Some(0), 1, 7),
+            (3, 1, Some(1), 0, 0),
+            (4, 1, Some(1), 0, 8),
+        ]
+    );
+}
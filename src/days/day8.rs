@@ -3,7 +3,9 @@
 // https://adventofcode.com/2019/day/8
 //
 //
-use console::style;
+use super::{parsed, Answer, Parsed, Solution};
+use crate::parse;
+use std::error::Error;
 
 type Bytes = Vec<u8>;
 
@@ -27,17 +29,17 @@ impl Layer {
         c
     }
 
-    fn draw(&self, w: usize) {
-        for chunk in self.data.chunks(w) {
-            for c in chunk {
-                if *c == 0 {
-                    print!("{}", style(c).black());
-                } else {
-                    print!("{}", style(c).white());
-                }
-            }
-            println!();
-        }
+    fn draw(&self, w: usize) -> String {
+        self.data
+            .chunks(w)
+            .map(|chunk| {
+                chunk
+                    .iter()
+                    .map(|c| if *c == 0 { ' ' } else { '#' })
+                    .collect::<String>()
+            })
+            .collect::<Vec<_>>()
+            .join("\n")
     }
 }
 
@@ -52,7 +54,7 @@ impl SpaceImage {
         let wsz = w * h;
         let mut layers = vec![];
         for chunk in data.chunks(wsz) {
-            layers.push(Layer::new(chunk.iter().copied().collect()))
+            layers.push(Layer::new(chunk.to_vec()))
         }
 
         SpaceImage {
@@ -62,11 +64,11 @@ impl SpaceImage {
         }
     }
 
-    fn layers(&mut self) -> &mut Vec<Layer> {
-        &mut self.layers
+    fn layers(&self) -> &[Layer] {
+        &self.layers
     }
 
-    fn decode_image(&mut self) -> Layer {
+    fn decode_image(&self) -> Layer {
         let mut image = vec![];
         for idx in 0..(self.width * self.height) {
             let pixels = self.layers.iter().map(|x| x.data[idx]).collect::<Vec<u8>>();
@@ -91,20 +93,43 @@ impl SpaceImage {
     }
 }
 
-fn main() {
-    let raw_image_data = include_str!("input8.txt")
-        .chars()
-        .map(|x| x.to_digit(10).unwrap() as u8)
-        .collect::<Vec<_>>();
-    let mut image = SpaceImage::new(raw_image_data, 25, 6);
-    let layer = image
-        .layers()
-        .iter()
-        .min_by(|x, y| x.count(0).cmp(&y.count(0)))
-        .unwrap();
-    println!("Part one: {}", layer.count(1) * layer.count(2));
-    println!("Part two");
-    image.decode_image().draw(25);
+/// A 25x6 image, which has to be made of whole layers
+fn parse_image(input: &str) -> Result<SpaceImage, Box<dyn Error>> {
+    let data = parse::digits(input)?;
+    if data.is_empty() || data.len() % (25 * 6) != 0 {
+        return Err(format!("{} pixels do not make whole 25x6 layers", data.len()).into());
+    }
+    Ok(SpaceImage::new(data, 25, 6))
+}
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    fn day(&self) -> u8 {
+        8
+    }
+
+    fn title(&self) -> &'static str {
+        "Space Image Format"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_image(input)?))
+    }
+
+    fn part_one(&self, input: &Parsed) -> Answer {
+        let layer = parsed::<SpaceImage>(input)?
+            .layers()
+            .iter()
+            .min_by(|x, y| x.count(0).cmp(&y.count(0)))
+            .ok_or("image has no layers")?;
+        Ok((layer.count(1) * layer.count(2)).to_string())
+    }
+
+    /// The decoded message, one line per row of pixels
+    fn part_two(&self, input: &Parsed) -> Answer {
+        Ok(parsed::<SpaceImage>(input)?.decode_image().draw(25))
+    }
 }
 
 #[test]
@@ -120,7 +145,8 @@ fn test_sif_layers() {
 
 #[test]
 fn test_image_decoding() {
-    let mut image = SpaceImage::new(vec![0, 2, 2, 2, 1, 1, 2, 2, 2, 2, 1, 2, 0, 0, 0, 0], 2, 2);
+    let image = SpaceImage::new(vec![0, 2, 2, 2, 1, 1, 2, 2, 2, 2, 1, 2, 0, 0, 0, 0], 2, 2);
     let layer = image.decode_image();
     assert_eq!(Layer::new(vec![0, 1, 1, 0]), layer);
+    assert!(Day8.part(2, "0123").is_err());
 }
@@ -0,0 +1,61 @@
+// --- Day 9: Sensor Boost ---
+//
+// https://adventofcode.com/2019/day/9
+//
+
+use super::{parsed, Answer, Parsed, Solution};
+use crate::intcode::{parse_program, IntCodeComputer, LargeNumber, Program, QueueIo};
+use std::error::Error;
+
+fn boost(prog: &Program, mode: LargeNumber) -> Answer {
+    let mut computer = IntCodeComputer::with_io(prog.clone(), QueueIo::new(vec![mode]));
+    computer.run()?;
+    match computer.io.output.len() {
+        1 => Ok(computer.io.output[0].to_string()),
+        // anything else lists the opcodes that are not working
+        _ => Err(format!("malfunctioning opcodes: {:?}", computer.io.output).into()),
+    }
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    fn day(&self) -> u8 {
+        9
+    }
+
+    fn title(&self) -> &'static str {
+        "Sensor Boost"
+    }
+
+    fn parse(&self, input: &str) -> Result<Parsed, Box<dyn Error>> {
+        Ok(Box::new(parse_program(input)?))
+    }
+
+    /// BOOST keycode in test mode
+    fn part_one(&self, input: &Parsed) -> Answer {
+        boost(parsed(input)?, 1)
+    }
+
+    /// Distress signal coordinates in sensor boost mode
+    fn part_two(&self, input: &Parsed) -> Answer {
+        boost(parsed(input)?, 2)
+    }
+}
+
+#[test]
+fn test_large_numbers() {
+    let mut c = IntCodeComputer::with_io(vec![104, 1125899906842624, 99], QueueIo::default());
+    c.run().unwrap();
+    assert_eq!(c.io.output, vec![1125899906842624]);
+}
+
+#[test]
+fn test_self_copier() {
+    let prog = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut c = IntCodeComputer::with_io(prog.clone(), QueueIo::default());
+    c.run().unwrap();
+    assert_eq!(c.io.output, prog);
+}
@@ -0,0 +1,142 @@
+// Puzzle inputs, loaded at runtime.
+//
+// A day's input comes from an explicit file, stdin, or a directory of
+// `inputN.txt` files. The directory defaults to `$AOC_INPUTS`, falling
+// back to the `inputs` directory of this crate.
+//
+use std::fmt;
+use std::fs;
+use std::io::{self, Read};
+use std::path::PathBuf;
+
+/// Environment variable naming the inputs directory
+pub const INPUTS_ENV: &str = "AOC_INPUTS";
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Source {
+    File(PathBuf),
+    Stdin,
+    /// Holds `inputN.txt` for every day N
+    Dir(PathBuf),
+}
+
+impl Source {
+    /// A path, or `-` for stdin
+    pub fn from_arg(arg: &str) -> Source {
+        match arg {
+            "-" => Source::Stdin,
+            path => Source::File(PathBuf::from(path)),
+        }
+    }
+
+    /// `$AOC_INPUTS`, or the crate's own inputs
+    pub fn default_dir() -> Source {
+        match std::env::var_os(INPUTS_ENV) {
+            Some(dir) if !dir.is_empty() => Source::Dir(PathBuf::from(dir)),
+            _ => Source::Dir(PathBuf::from(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/inputs"
+            ))),
+        }
+    }
+
+    /// Where the input of `day` is read from
+    pub fn describe(&self, day: u8) -> String {
+        match self {
+            Source::File(path) => path.display().to_string(),
+            Source::Stdin => "<stdin>".to_string(),
+            Source::Dir(dir) => dir.join(file_name(day)).display().to_string(),
+        }
+    }
+}
+
+fn file_name(day: u8) -> String {
+    format!("input{}.txt", day)
+}
+
+#[derive(Debug)]
+pub enum InputError {
+    Missing { day: u8, path: PathBuf },
+    Unreadable { source: String, error: io::Error },
+    Empty { source: String },
+}
+
+impl fmt::Display for InputError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::Missing { day, path } => write!(
+                f,
+                "{}: no input for day {}; pass --input <path> or set {} to a directory of inputN.txt files",
+                path.display(),
+                day,
+                INPUTS_ENV
+            ),
+            Self::Unreadable { source, error } => write!(f, "{}: {}", source, error),
+            Self::Empty { source } => write!(f, "{}: input is empty", source),
+        }
+    }
+}
+
+impl std::error::Error for InputError {}
+
+pub fn load(day: u8, source: &Source) -> Result<String, InputError> {
+    let name = source.describe(day);
+    let unreadable = |error| InputError::Unreadable {
+        source: name.clone(),
+        error,
+    };
+    let text = match source {
+        Source::Stdin => {
+            let mut text = String::new();
+            io::stdin().read_to_string(&mut text).map_err(unreadable)?;
+            text
+        }
+        Source::File(path) => fs::read_to_string(path).map_err(unreadable)?,
+        Source::Dir(dir) => {
+            let path = dir.join(file_name(day));
+            match fs::read_to_string(&path) {
+                Err(e) if e.kind() == io::ErrorKind::NotFound => {
+                    return Err(InputError::Missing { day, path })
+                }
+                res => res.map_err(unreadable)?,
+            }
+        }
+    };
+    if text.trim().is_empty() {
+        return Err(InputError::Empty { source: name });
+    }
+    Ok(text)
+}
+
+#[test]
+fn test_load() {
+    let dir = std::env::temp_dir().join(format!("aoc-inputs-{}", std::process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(dir.join("input1.txt"), "12\n14\n").unwrap();
+    fs::write(dir.join("input2.txt"), " \n").unwrap();
+    let source = Source::Dir(dir.clone());
+
+    assert_eq!(load(1, &source).unwrap(), "12\n14\n");
+    assert!(matches!(load(2, &source), Err(InputError::Empty { .. })));
+    let missing = load(3, &source).unwrap_err();
+    assert!(matches!(missing, InputError::Missing { day: 3, .. }));
+    assert!(missing
+        .to_string()
+        .contains("input3.txt: no input for day 3"));
+    assert_eq!(
+        load(
+            1,
+            &Source::from_arg(&dir.join("input1.txt").to_string_lossy())
+        )
+        .unwrap(),
+        "12\n14\n"
+    );
+    assert!(matches!(
+        load(
+            1,
+            &Source::from_arg(&dir.join("nope.txt").to_string_lossy())
+        ),
+        Err(InputError::Unreadable { .. })
+    ));
+    fs::remove_dir_all(&dir).unwrap();
+}
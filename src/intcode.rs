@@ -0,0 +1,834 @@
+// Shared Intcode computer used by days 2, 5, 7 and 9.
+//
+// https://adventofcode.com/2019/day/9
+//
+
+mod asm;
+mod cfg;
+mod debugger;
+mod disasm;
+mod error;
+mod io;
+mod limits;
+mod memory;
+mod network;
+mod snapshot;
+mod solver;
+mod symbolic;
+mod trace;
+
+pub use asm::{assemble, AsmError};
+pub use cfg::{Block, ControlFlowGraph, Edge, Finding};
+pub use debugger::{Debugger, Stop};
+pub use disasm::{decode_at, disassemble, listing, Entry};
+pub use error::IntcodeError;
+pub use io::{CallbackIo, ChannelIo, IntcodeIo, IoError, IterIo, QueueIo, StdIo};
+pub use limits::{Limit, Limits, TIME_CHECK_INTERVAL};
+pub use memory::{MemoryBackend, MemoryStats, OutOfMemory, PagedMemory, PAGE_SIZE};
+pub use network::{
+    Delivery, Machine, MachineReport, MachineStatus, Network, NetworkReport, Topology,
+    UnknownMachine,
+};
+pub use snapshot::Snapshot;
+pub use solver::{Check, Linear, Model, Rel};
+pub use symbolic::{check, Constraint, End, Expr, Path, Symbolic, Var};
+pub use trace::{Profile, TraceRecord, Tracer};
+
+use crate::parse::ParseError;
+use std::convert::TryFrom;
+use std::time::{Duration, Instant};
+
+pub type LargeNumber = i64;
+pub type Memory = Vec<LargeNumber>;
+pub type Program = Vec<LargeNumber>;
+
+#[allow(clippy::upper_case_acronyms)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Opcode {
+    ADD,
+    MUL,
+    STO,
+    LOAD,
+    HLT,
+    JNZ,
+    JZ,
+    LT,
+    EQ,
+    RBO,
+}
+impl Opcode {
+    pub fn from_i64(n: i64) -> Option<Opcode> {
+        match n {
+            1 => Some(Self::ADD),
+            2 => Some(Self::MUL),
+            3 => Some(Self::STO),
+            4 => Some(Self::LOAD),
+            5 => Some(Self::JNZ),
+            6 => Some(Self::JZ),
+            7 => Some(Self::LT),
+            8 => Some(Self::EQ),
+            9 => Some(Self::RBO),
+            99 => Some(Self::HLT),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> LargeNumber {
+        match self {
+            Self::ADD => 1,
+            Self::MUL => 2,
+            Self::STO => 3,
+            Self::LOAD => 4,
+            Self::JNZ => 5,
+            Self::JZ => 6,
+            Self::LT => 7,
+            Self::EQ => 8,
+            Self::RBO => 9,
+            Self::HLT => 99,
+        }
+    }
+
+    /// Number of parameters following the opcode
+    pub fn arity(self) -> usize {
+        match self {
+            Self::HLT => 0,
+            Self::STO | Self::LOAD | Self::RBO => 1,
+            Self::JNZ | Self::JZ => 2,
+            Self::ADD | Self::MUL | Self::LT | Self::EQ => 3,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum AddressingMode {
+    Immediate,
+    Position,
+    Relative,
+}
+
+impl AddressingMode {
+    pub fn from_i64(n: i64) -> Option<AddressingMode> {
+        match n {
+            0 => Some(Self::Position),
+            1 => Some(Self::Immediate),
+            2 => Some(Self::Relative),
+            _ => None,
+        }
+    }
+
+    pub fn code(self) -> LargeNumber {
+        match self {
+            Self::Position => 0,
+            Self::Immediate => 1,
+            Self::Relative => 2,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ComputerState {
+    Running,
+    WaitingForInput,
+    Halted,
+    /// Stopped before the instruction at the pc, see `Limits`
+    Stopped(Limit),
+}
+
+/// What a single step (or a run of steps) of the computer ended with
+#[derive(Debug, PartialEq)]
+pub enum StepOutcome {
+    Stepped,
+    /// `STO` found no input; the pc stays on the instruction so it
+    /// is retried once input becomes available
+    NeedsInput,
+    /// `LOAD` produced a value, which has also been sent to the io sink
+    Output(LargeNumber),
+    Halted,
+}
+
+pub struct IntCodeComputer<IO = StdIo, M = Memory> {
+    pub pc: usize,
+    pub mem: M,
+    pub state: ComputerState,
+    pub io: IO,
+    pc_mod: bool,
+    relbase: LargeNumber,
+    ins_pc: usize,
+    steps: usize,
+    limits: Limits,
+    deadline: Option<Instant>,
+    /// Whether any limit is set, keeping the checks off the fast path
+    limited: bool,
+    tracer: Option<Tracer>,
+    /// Decoded instructions by address, with the cell value they came from
+    decoded: Vec<Option<(LargeNumber, Instruction)>>,
+    decode_cache: bool,
+}
+
+/// Highest address whose decoded instruction is cached, plus one
+const DECODE_CACHE_SIZE: usize = 1 << 16;
+
+impl IntCodeComputer {
+    pub fn new(mem: Memory) -> IntCodeComputer {
+        IntCodeComputer::with_io(mem, StdIo)
+    }
+}
+
+impl<IO: IntcodeIo> IntCodeComputer<IO> {
+    pub fn with_io(mem: Memory, io: IO) -> IntCodeComputer<IO> {
+        IntCodeComputer::with_memory(mem, io)
+    }
+}
+
+impl<IO: IntcodeIo, M: MemoryBackend> IntCodeComputer<IO, M> {
+    /// Runs on any memory backend, e.g. `PagedMemory` for huge addresses
+    pub fn with_memory(mem: M, io: IO) -> IntCodeComputer<IO, M> {
+        IntCodeComputer {
+            pc: 0,
+            mem,
+            state: ComputerState::Running,
+            io,
+            pc_mod: false,
+            relbase: 0,
+            ins_pc: 0,
+            steps: 0,
+            limits: Limits::default(),
+            deadline: None,
+            limited: false,
+            tracer: None,
+            decoded: vec![],
+            decode_cache: true,
+        }
+    }
+
+    /// Replaces every limit, the time limit counting from now
+    pub fn with_limits(&mut self, limits: Limits) -> &mut Self {
+        self.limits = limits;
+        self.deadline = limits.time.map(|t| Instant::now() + t);
+        self.limited = limits != Limits::default();
+        self
+    }
+
+    /// Stops once `limit` instructions have run
+    pub fn with_step_limit(&mut self, limit: usize) -> &mut Self {
+        self.limits.steps = Some(limit);
+        self.limited = true;
+        self
+    }
+
+    /// Stops before writing to an address of `cells` or more
+    pub fn with_memory_limit(&mut self, cells: usize) -> &mut Self {
+        self.limits.memory = Some(cells);
+        self.limited = true;
+        self
+    }
+
+    /// Stops once `limit` has passed from now
+    pub fn with_time_limit(&mut self, limit: Duration) -> &mut Self {
+        self.limits.time = Some(limit);
+        self.deadline = Some(Instant::now() + limit);
+        self.limited = true;
+        self
+    }
+
+    pub fn limits(&self) -> Limits {
+        self.limits
+    }
+
+    /// Turns caching of decoded instructions on or off, it is on by default
+    pub fn with_decode_cache(&mut self, enabled: bool) -> &mut Self {
+        self.decode_cache = enabled;
+        self.decoded.clear();
+        self
+    }
+
+    pub fn relbase(&self) -> LargeNumber {
+        self.relbase
+    }
+
+    /// Records every executed instruction from now on
+    pub fn with_tracer(&mut self, tracer: Tracer) -> &mut Self {
+        self.tracer = Some(tracer);
+        self
+    }
+
+    pub fn tracer(&self) -> Option<&Tracer> {
+        self.tracer.as_ref()
+    }
+
+    /// Detaches the tracer, e.g. to `finish` it
+    pub fn take_tracer(&mut self) -> Option<Tracer> {
+        self.tracer.take()
+    }
+
+    pub fn memory_stats(&self) -> MemoryStats {
+        self.mem.stats()
+    }
+
+    /// Number of instructions executed so far
+    pub fn steps(&self) -> usize {
+        self.steps
+    }
+
+    /// Runs until the computer halts, failing if it runs out of input
+    pub fn run(&mut self) -> Result<(), IntcodeError> {
+        match self.run_until(|_| false)? {
+            StepOutcome::NeedsInput => Err(IntcodeError::InputExhausted { pc: self.pc }),
+            _ => Ok(()),
+        }
+    }
+
+    /// Runs until the computer halts, needs input or `stop` returns
+    /// true for the outcome of a step
+    pub fn run_until<F>(&mut self, mut stop: F) -> Result<StepOutcome, IntcodeError>
+    where
+        F: FnMut(&StepOutcome) -> bool,
+    {
+        loop {
+            let outcome = self.step()?;
+            match outcome {
+                StepOutcome::NeedsInput | StepOutcome::Halted => return Ok(outcome),
+                _ if stop(&outcome) => return Ok(outcome),
+                _ => {}
+            }
+        }
+    }
+
+    /// Executes a single instruction
+    pub fn step(&mut self) -> Result<StepOutcome, IntcodeError> {
+        if self.state == ComputerState::Halted {
+            return Ok(StepOutcome::Halted);
+        }
+        let ins = self.decode()?;
+        if self.limited {
+            self.check_limits(ins)?;
+        }
+        self.state = ComputerState::Running;
+        self.pc_mod = false;
+        self.ins_pc = self.pc;
+        if self.tracer.is_some() {
+            let params = (1..=ins.opcode.arity())
+                .map(|i| self.load(self.pc + i))
+                .collect();
+            if let Some(t) = self.tracer.as_mut() {
+                t.begin(self.pc, ins, params);
+            }
+        }
+        let outcome = self.execute(ins)?;
+        if !self.pc_mod {
+            self.increment_pc();
+        }
+        if outcome != StepOutcome::NeedsInput {
+            self.steps += 1;
+            if let Some(t) = self.tracer.as_mut() {
+                t.end();
+            }
+        }
+        Ok(outcome)
+    }
+
+    /// Entries are only used while the cell still holds the value they were
+    /// decoded from, so writes to code invalidate them, including writes to
+    /// `mem` from outside the computer
+    fn decode(&mut self) -> Result<Instruction, IntcodeError> {
+        let value = self.load(self.pc);
+        if !self.decode_cache || self.pc >= DECODE_CACHE_SIZE {
+            return Instruction::decode(value, self.pc);
+        }
+        if let Some(Some((cached, ins))) = self.decoded.get(self.pc) {
+            if *cached == value {
+                return Ok(*ins);
+            }
+        }
+        let ins = Instruction::decode(value, self.pc)?;
+        if self.pc >= self.decoded.len() {
+            self.decoded.resize(self.pc + 1, None);
+        }
+        self.decoded[self.pc] = Some((value, ins));
+        Ok(ins)
+    }
+
+    /// Stops the computer if running `ins` would exceed a limit
+    #[inline(never)]
+    fn check_limits(&mut self, ins: Instruction) -> Result<(), IntcodeError> {
+        let mut exceeded = None;
+        if let Some(limit) = self.limits.steps.filter(|l| self.steps >= *l) {
+            exceeded = Some(Limit::Steps(limit));
+        }
+        if let (Some(limit), Some(deadline)) = (self.limits.time, self.deadline) {
+            if self.steps % TIME_CHECK_INTERVAL == 0 && Instant::now() >= deadline {
+                exceeded = exceeded.or(Some(Limit::Time(limit)));
+            }
+        }
+        if let Some(cells) = self.limits.memory {
+            if self.write_target(ins).map_or(false, |addr| addr >= cells) {
+                exceeded = exceeded.or(Some(Limit::Memory(cells)));
+            }
+        }
+        match exceeded {
+            Some(limit) => {
+                self.state = ComputerState::Stopped(limit);
+                Err(IntcodeError::LimitExceeded { pc: self.pc, limit })
+            }
+            None => Ok(()),
+        }
+    }
+
+    /// Address the instruction at the pc writes to, if any
+    fn write_target(&self, ins: Instruction) -> Option<usize> {
+        let (mode, offset) = match ins.opcode {
+            Opcode::STO => (ins.pm1, 1),
+            Opcode::ADD | Opcode::MUL | Opcode::LT | Opcode::EQ => (ins.pm3, 3),
+            _ => return None,
+        };
+        let param = self.load(self.pc + offset);
+        match mode {
+            AddressingMode::Position => usize::try_from(param).ok(),
+            AddressingMode::Relative => param
+                .checked_add(self.relbase)
+                .and_then(|a| usize::try_from(a).ok()),
+            AddressingMode::Immediate => None,
+        }
+    }
+
+    fn execute(&mut self, ins: Instruction) -> Result<StepOutcome, IntcodeError> {
+        match ins.opcode {
+            Opcode::HLT => {
+                self.state = ComputerState::Halted;
+                self.pc_mod = true;
+                return Ok(StepOutcome::Halted);
+            }
+            Opcode::ADD => {
+                let left_operand = self.read_mem(ins.pm1)?;
+                let right_operand = self.read_mem(ins.pm2)?;
+                let sum = left_operand.checked_add(right_operand);
+                self.write_mem(sum.ok_or(self.overflow())?, ins.pm3)?
+            }
+            Opcode::MUL => {
+                let left_operand = self.read_mem(ins.pm1)?;
+                let right_operand = self.read_mem(ins.pm2)?;
+                let product = left_operand.checked_mul(right_operand);
+                self.write_mem(product.ok_or(self.overflow())?, ins.pm3)?
+            }
+            Opcode::STO => match self.io.input().map_err(|e| self.io_error(e))? {
+                Some(inp) => self.write_mem(inp, ins.pm1)?,
+                None => {
+                    self.state = ComputerState::WaitingForInput;
+                    self.pc_mod = true;
+                    return Ok(StepOutcome::NeedsInput);
+                }
+            },
+            Opcode::LOAD => {
+                let val = self.read_mem(ins.pm1)?;
+                self.io.output(val).map_err(|e| self.io_error(e))?;
+                return Ok(StepOutcome::Output(val));
+            }
+            Opcode::JNZ => {
+                let lft = self.read_mem(ins.pm1)?;
+                let rgt = self.read_mem(ins.pm2)?;
+                if lft != 0 {
+                    self.pc = self.address(rgt)?;
+                    self.pc_mod = true;
+                }
+            }
+            Opcode::JZ => {
+                let lft = self.read_mem(ins.pm1)?;
+                let rgt = self.read_mem(ins.pm2)?;
+                if lft == 0 {
+                    self.pc = self.address(rgt)?;
+                    self.pc_mod = true;
+                }
+            }
+            Opcode::EQ => {
+                let lft = self.read_mem(ins.pm1)?;
+                let rgt = self.read_mem(ins.pm2)?;
+                if lft == rgt {
+                    self.write_mem(1, ins.pm3)?;
+                } else {
+                    self.write_mem(0, ins.pm3)?;
+                }
+            }
+            Opcode::LT => {
+                let lft = self.read_mem(ins.pm1)?;
+                let rgt = self.read_mem(ins.pm2)?;
+                if lft < rgt {
+                    self.write_mem(1, ins.pm3)?;
+                } else {
+                    self.write_mem(0, ins.pm3)?;
+                }
+            }
+            Opcode::RBO => {
+                let offset = self.read_mem(ins.pm1)?;
+                self.relbase = self.relbase.checked_add(offset).ok_or(self.overflow())?;
+            }
+        }
+        Ok(StepOutcome::Stepped)
+    }
+
+    fn increment_pc(&mut self) {
+        self.pc += 1;
+    }
+
+    fn load(&self, addr: usize) -> LargeNumber {
+        self.mem.load(addr)
+    }
+
+    fn address(&self, addr: LargeNumber) -> Result<usize, IntcodeError> {
+        if addr < 0 {
+            return Err(IntcodeError::NegativeAddress {
+                pc: self.ins_pc,
+                addr,
+            });
+        }
+        Ok(addr as usize)
+    }
+
+    fn relative(&self, param: LargeNumber) -> Result<usize, IntcodeError> {
+        self.address(param.checked_add(self.relbase).ok_or(self.overflow())?)
+    }
+
+    fn io_error(&self, error: IoError) -> IntcodeError {
+        IntcodeError::Io {
+            pc: self.ins_pc,
+            error,
+        }
+    }
+
+    fn overflow(&self) -> IntcodeError {
+        IntcodeError::Overflow { pc: self.ins_pc }
+    }
+
+    fn read_mem(&mut self, mode: AddressingMode) -> Result<LargeNumber, IntcodeError> {
+        self.increment_pc();
+        let param = self.load(self.pc);
+        let value = match mode {
+            AddressingMode::Immediate => param,
+            AddressingMode::Position => self.load(self.address(param)?),
+            AddressingMode::Relative => self.load(self.relative(param)?),
+        };
+        if let Some(t) = self.tracer.as_mut() {
+            t.read(value);
+        }
+        Ok(value)
+    }
+
+    fn write_mem(&mut self, value: LargeNumber, mode: AddressingMode) -> Result<(), IntcodeError> {
+        self.increment_pc();
+        let param = self.load(self.pc);
+        let addr = match mode {
+            AddressingMode::Position => self.address(param)?,
+            AddressingMode::Relative => self.relative(param)?,
+            AddressingMode::Immediate => {
+                return Err(IntcodeError::ImmediateWrite { pc: self.ins_pc })
+            }
+        };
+        let pc = self.ins_pc;
+        self.mem
+            .store(addr, value)
+            .map_err(|e| IntcodeError::OutOfMemory { pc, addr: e.addr })?;
+        if let Some(t) = self.tracer.as_mut() {
+            t.write(addr, value);
+        }
+        Ok(())
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Instruction {
+    pub opcode: Opcode,
+    pub pm1: AddressingMode,
+    pub pm2: AddressingMode,
+    pub pm3: AddressingMode,
+}
+impl Instruction {
+    /// Decodes the instruction value `i` found at address `pc`
+    pub fn decode(i: LargeNumber, pc: usize) -> Result<Instruction, IntcodeError> {
+        let opcode =
+            Opcode::from_i64(i % 100).ok_or(IntcodeError::UnknownOpcode { pc, opcode: i })?;
+        let mode = |n: u32| {
+            let m = i / 100 / 10i64.pow(n) % 10;
+            AddressingMode::from_i64(m).ok_or(IntcodeError::InvalidMode { pc, mode: m })
+        };
+        if i < 0 || i / 100_000 != 0 {
+            return Err(IntcodeError::InvalidMode { pc, mode: i / 100 });
+        }
+        Ok(Instruction {
+            pm3: mode(2)?,
+            pm2: mode(1)?,
+            pm1: mode(0)?,
+            opcode,
+        })
+    }
+
+    /// Addressing modes of the parameters the opcode actually takes
+    pub fn modes(&self) -> Vec<AddressingMode> {
+        let mut modes = vec![self.pm1, self.pm2, self.pm3];
+        modes.truncate(self.opcode.arity());
+        modes
+    }
+}
+
+/// Parses the comma separated program format of the puzzle inputs
+pub fn parse_program(s: &str) -> Result<Program, ParseError> {
+    crate::parse::ints(s)
+}
+
+/// Renders a program back into the comma separated input format
+pub fn format_program(prog: &[LargeNumber]) -> String {
+    prog.iter()
+        .map(|x| x.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+#[test]
+fn test_memory_grows_on_write_only() {
+    let mut c = IntCodeComputer::new(vec![1, 0, 0, 0, 99]);
+    c.run().unwrap();
+    assert_eq!(c.mem, vec![2, 0, 0, 0, 99]);
+
+    let mut c = IntCodeComputer::new(vec![1101, 2, 3, 10, 99]);
+    c.run().unwrap();
+    assert_eq!(c.mem.len(), 11);
+    assert_eq!(c.mem[10], 5);
+}
+
+#[test]
+fn test_paged_memory_backend() {
+    let prog = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let mut c = IntCodeComputer::with_memory(PagedMemory::new(&prog), QueueIo::default());
+    c.run().unwrap();
+    assert_eq!(c.io.output, prog);
+
+    let mut c = IntCodeComputer::with_memory(
+        PagedMemory::new(&[109, 1_000_000_000_000, 21101, 3, 4, 0, 99]),
+        QueueIo::default(),
+    );
+    c.run().unwrap();
+    assert_eq!(c.mem.load(1_000_000_000_000), 7);
+    assert_eq!(c.memory_stats().pages, 2);
+}
+
+#[test]
+fn test_decode_cache() {
+    // outputs 7, turns its first instruction into `HLT` and jumps back to it
+    let prog = vec![104, 7, 1101, 99, 0, 0, 1105, 1, 0];
+    for cache in [true, false] {
+        let mut c = IntCodeComputer::with_io(prog.clone(), QueueIo::default());
+        c.with_decode_cache(cache).with_step_limit(10);
+        assert_eq!(c.run(), Ok(()));
+        assert_eq!(c.io.output, vec![7]);
+        assert_eq!(c.steps(), 4);
+    }
+
+    // rewritten from outside while waiting for input
+    let mut c = IntCodeComputer::with_io(vec![104, 1, 3, 7, 1105, 1, 0, 0], QueueIo::default());
+    assert_eq!(c.run_until(|_| false), Ok(StepOutcome::NeedsInput));
+    c.mem[0] = 99;
+    c.io.input.push_back(5);
+    c.with_step_limit(10);
+    assert_eq!(c.run(), Ok(()));
+    assert_eq!(c.io.output, vec![1]);
+}
+
+#[test]
+fn test_relative_mode_io() {
+    let mut c = IntCodeComputer::with_io(vec![109, 10, 203, 0, 204, 0, 99], QueueIo::new(vec![42]));
+    c.run().unwrap();
+    assert_eq!(c.io.output, vec![42]);
+}
+
+#[test]
+fn test_pause_on_input() {
+    let mut c = IntCodeComputer::with_io(vec![3, 9, 4, 9, 3, 9, 4, 9, 99, 0], QueueIo::default());
+    assert_eq!(c.run_until(|_| false), Ok(StepOutcome::NeedsInput));
+    assert_eq!(c.state, ComputerState::WaitingForInput);
+    assert_eq!(c.pc, 0);
+    c.io.input.push_back(7);
+    assert_eq!(
+        c.run_until(|o| matches!(o, StepOutcome::Output(_))),
+        Ok(StepOutcome::Output(7))
+    );
+    assert_eq!(c.run(), Err(IntcodeError::InputExhausted { pc: 4 }));
+    c.io.input.push_back(8);
+    assert_eq!(c.run(), Ok(()));
+    assert_eq!(c.state, ComputerState::Halted);
+    assert_eq!(c.io.output, vec![7, 8]);
+}
+
+/// Day 7's feedback loop example, an amplifier that reads its phase and
+/// then sends `2 * input + phase - 4` for each of five inputs
+#[cfg(test)]
+pub const FEEDBACK_LOOP: [LargeNumber; 29] = [
+    3, 26, 1001, 26, -4, 26, 3, 27, 1002, 27, 2, 27, 1, 27, 26, 27, 4, 27, 1001, 28, -1, 28, 1005,
+    28, 6, 99, 0, 0, 5,
+];
+
+#[test]
+fn test_cooperative_feedback_loop() {
+    let mut amps = [9, 8, 7, 6, 5]
+        .iter()
+        .map(|phase| IntCodeComputer::with_io(FEEDBACK_LOOP.to_vec(), QueueIo::new(vec![*phase])))
+        .collect::<Vec<_>>();
+    // one pass on a single thread: each amplifier pauses on its output,
+    // then again on its next read
+    let mut signal = 0;
+    for amp in amps.iter_mut() {
+        amp.io.input.push_back(signal);
+        match amp.run_until(|o| matches!(o, StepOutcome::Output(_))) {
+            Ok(StepOutcome::Output(v)) => signal = v,
+            res => panic!("expected an output, got {:?}", res),
+        }
+        assert_eq!(amp.run_until(|_| false), Ok(StepOutcome::NeedsInput));
+        assert_eq!((amp.state, amp.pc), (ComputerState::WaitingForInput, 6));
+    }
+    assert_eq!(signal, 129);
+    // and resumes from there with the signal fed back
+    amps[0].io.input.push_back(signal);
+    assert_eq!(
+        amps[0].run_until(|o| matches!(o, StepOutcome::Output(_))),
+        Ok(StepOutcome::Output(263))
+    );
+}
+
+#[test]
+fn test_errors() {
+    let run = |prog: Program| IntCodeComputer::with_io(prog, QueueIo::default()).run();
+    assert_eq!(
+        run(vec![1, 0, 0, 0, 42]),
+        Err(IntcodeError::UnknownOpcode { pc: 4, opcode: 42 })
+    );
+    assert_eq!(
+        run(vec![1301, 0, 0, 0, 99]),
+        Err(IntcodeError::InvalidMode { pc: 0, mode: 3 })
+    );
+    assert_eq!(
+        run(vec![11101, 1, 1, 0, 99]),
+        Err(IntcodeError::ImmediateWrite { pc: 0 })
+    );
+    assert_eq!(
+        run(vec![109, -5, 1202, 0, 1, 0, 99]),
+        Err(IntcodeError::NegativeAddress { pc: 2, addr: -5 })
+    );
+    assert_eq!(
+        run(vec![1106, 0, -1]),
+        Err(IntcodeError::NegativeAddress { pc: 0, addr: -1 })
+    );
+    assert_eq!(
+        run(vec![109, LargeNumber::MAX, 21101, 1, 1, 1, 99]),
+        Err(IntcodeError::Overflow { pc: 2 })
+    );
+    assert_eq!(
+        run(vec![1102, LargeNumber::MAX, 2, 0, 99]),
+        Err(IntcodeError::Overflow { pc: 0 })
+    );
+    assert_eq!(
+        run(vec![109, LargeNumber::MAX, 109, 1, 99]),
+        Err(IntcodeError::Overflow { pc: 2 })
+    );
+
+    // the receiving end is gone
+    let (outbox, _) = std::sync::mpsc::channel();
+    let (_, inbox) = std::sync::mpsc::channel();
+    let mut c =
+        IntCodeComputer::with_io(vec![1, 0, 0, 0, 104, 7, 99], ChannelIo::new(inbox, outbox));
+    let err = c.run().unwrap_err();
+    assert_eq!(err.pc(), 4);
+    assert_eq!(err.to_string(), "outbox closed, 7 was not sent at 4");
+}
+
+#[test]
+fn test_limits() {
+    let mut c = IntCodeComputer::with_io(vec![1105, 1, 0], QueueIo::default());
+    c.with_step_limit(10);
+    let stopped = IntcodeError::LimitExceeded {
+        pc: 0,
+        limit: Limit::Steps(10),
+    };
+    assert_eq!(c.run(), Err(stopped.clone()));
+    assert_eq!(c.state, ComputerState::Stopped(Limit::Steps(10)));
+    assert_eq!(c.steps(), 10);
+    assert_eq!(stopped.to_string(), "step limit of 10 exceeded at 0");
+    assert_eq!(c.step(), Err(stopped));
+
+    // the write to 1000 is not made, and resumes once the limit is raised
+    let mut c = IntCodeComputer::with_io(
+        vec![1101, 2, 3, 9, 1101, 4, 5, 1000, 99, 0],
+        QueueIo::default(),
+    );
+    c.with_memory_limit(1000);
+    assert_eq!(
+        c.run(),
+        Err(IntcodeError::LimitExceeded {
+            pc: 4,
+            limit: Limit::Memory(1000)
+        })
+    );
+    assert_eq!((c.mem.len(), c.mem[9]), (10, 5));
+    c.with_memory_limit(1001);
+    assert_eq!(c.run(), Ok(()));
+    assert_eq!(c.mem[1000], 9);
+
+    // without a memory limit a write far out of reach fails instead of aborting
+    let mut c = IntCodeComputer::with_io(vec![1101, 1, 1, 1 << 62, 99], QueueIo::default());
+    assert_eq!(
+        c.run(),
+        Err(IntcodeError::OutOfMemory {
+            pc: 0,
+            addr: 1 << 62
+        })
+    );
+
+    let mut c = IntCodeComputer::with_io(vec![1105, 1, 0], QueueIo::default());
+    c.with_limits(Limits {
+        time: Some(Duration::from_millis(10)),
+        ..Limits::default()
+    });
+    let start = Instant::now();
+    assert!(matches!(
+        c.run(),
+        Err(IntcodeError::LimitExceeded {
+            limit: Limit::Time(_),
+            ..
+        })
+    ));
+    assert!(start.elapsed() < Duration::from_secs(5));
+    assert!(c.steps() % TIME_CHECK_INTERVAL == 0);
+}
+
+#[test]
+fn test_tracing() {
+    use std::sync::{Arc, Mutex};
+
+    #[derive(Clone, Default)]
+    struct Shared(Arc<Mutex<Vec<u8>>>);
+    impl std::io::Write for Shared {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0.lock().unwrap().write(buf)
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+
+    let buf = Shared::default();
+    let mut c = IntCodeComputer::with_io(vec![3, 7, 1001, 7, 1, 7, 99, 0], QueueIo::new(vec![4]));
+    c.with_tracer(Tracer::to_writer(buf.clone()));
+    c.run().unwrap();
+    let profile = c.take_tracer().unwrap().finish().unwrap();
+
+    let trace = String::from_utf8(buf.0.lock().unwrap().clone()).unwrap();
+    assert_eq!(
+        trace.lines().collect::<Vec<_>>(),
+        vec![
+            r#"{"step":0,"pc":0,"op":"STO","modes":"P","params":[7],"reads":[],"write":[7,4]}"#,
+            r#"{"step":1,"pc":2,"op":"ADD","modes":"PIP","params":[7,1,7],"reads":[4,1],"write":[7,5]}"#,
+            r#"{"step":2,"pc":6,"op":"HLT","modes":"","params":[],"reads":[],"write":null}"#,
+        ]
+    );
+    assert_eq!(profile.steps, 3);
+    assert_eq!(profile.opcodes[&Opcode::ADD], 1);
+    assert_eq!(profile.hottest(1), vec![(0, 1)]);
+}
@@ -0,0 +1,297 @@
+// Assembler for Intcode programs.
+//
+// The syntax mirrors the disassembler listings:
+//
+//     ; comments run to the end of the line
+//     start:  STO [rb+0]          ; labels end with a colon
+//             ADD [n], #-1, [n]
+//             JNZ [n], #start     ; labels resolve to addresses
+//             HLT
+//     n:      .data 3             ; raw cells
+//
+// Operands are `#value` (immediate), `[value]` (position) or
+// `[rb+offset]` (relative). Numeric labels such as `0004:` are treated as
+// address annotations and ignored, so listings assemble back into the
+// same program. Mode digits are only emitted for operands, so an
+// instruction with unused mode digits, such as `1104`, comes back in its
+// canonical form (`104`): the round trip preserves what the program does,
+// not every cell value.
+//
+use super::{AddressingMode, LargeNumber, Opcode, Program};
+use std::collections::HashMap;
+use std::fmt;
+
+/// Every variant carries the 1-based source line
+#[derive(Clone, Debug, PartialEq)]
+pub enum AsmError {
+    UnknownMnemonic {
+        line: usize,
+        mnemonic: String,
+    },
+    InvalidOperand {
+        line: usize,
+        operand: String,
+    },
+    OperandCount {
+        line: usize,
+        expected: usize,
+        found: usize,
+    },
+    ImmediateWrite {
+        line: usize,
+    },
+    UndefinedLabel {
+        line: usize,
+        label: String,
+    },
+    DuplicateLabel {
+        line: usize,
+        label: String,
+    },
+}
+
+impl fmt::Display for AsmError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownMnemonic { line, mnemonic } => {
+                write!(f, "line {}: unknown mnemonic `{}`", line, mnemonic)
+            }
+            Self::InvalidOperand { line, operand } => {
+                write!(f, "line {}: invalid operand `{}`", line, operand)
+            }
+            Self::OperandCount {
+                line,
+                expected,
+                found,
+            } => write!(
+                f,
+                "line {}: expected {} operands, found {}",
+                line, expected, found
+            ),
+            Self::ImmediateWrite { line } => {
+                write!(f, "line {}: write operand in immediate mode", line)
+            }
+            Self::UndefinedLabel { line, label } => {
+                write!(f, "line {}: undefined label `{}`", line, label)
+            }
+            Self::DuplicateLabel { line, label } => {
+                write!(f, "line {}: duplicate label `{}`", line, label)
+            }
+        }
+    }
+}
+
+impl std::error::Error for AsmError {}
+
+#[derive(Debug)]
+enum Value {
+    Num(LargeNumber),
+    Label(String),
+}
+
+fn parse_value(s: &str) -> Option<Value> {
+    let s = s.trim();
+    if let Ok(n) = s.parse() {
+        return Some(Value::Num(n));
+    }
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c.is_ascii_alphabetic() || c == '_' => {}
+        _ => return None,
+    }
+    if chars.all(|c| c.is_ascii_alphanumeric() || c == '_') {
+        Some(Value::Label(s.to_string()))
+    } else {
+        None
+    }
+}
+
+fn parse_operand(s: &str) -> Option<(AddressingMode, Value)> {
+    let s = s.trim();
+    if let Some(rest) = s.strip_prefix('#') {
+        return Some((AddressingMode::Immediate, parse_value(rest)?));
+    }
+    let inner = s.strip_prefix('[')?.strip_suffix(']')?.trim();
+    if inner == "rb" {
+        return Some((AddressingMode::Relative, Value::Num(0)));
+    }
+    if inner.starts_with("rb+") || inner.starts_with("rb-") {
+        let offset = inner[2..].replace(' ', "").parse().ok()?;
+        return Some((AddressingMode::Relative, Value::Num(offset)));
+    }
+    Some((AddressingMode::Position, parse_value(inner)?))
+}
+
+fn parse_mnemonic(s: &str) -> Option<Opcode> {
+    (1..=9)
+        .chain(Some(99))
+        .filter_map(Opcode::from_i64)
+        .find(|op| format!("{:?}", op).eq_ignore_ascii_case(s))
+}
+
+/// Whether the opcode writes through its last parameter
+fn writes_last(op: Opcode) -> bool {
+    matches!(
+        op,
+        Opcode::ADD | Opcode::MUL | Opcode::STO | Opcode::LT | Opcode::EQ
+    )
+}
+
+/// Assembles source text into a program
+pub fn assemble(src: &str) -> Result<Program, AsmError> {
+    let mut labels = HashMap::new();
+    let mut cells = vec![];
+
+    for (idx, raw) in src.lines().enumerate() {
+        let line = idx + 1;
+        let mut text = raw.split(';').next().unwrap_or("").trim();
+
+        while let Some(colon) = text.find(':') {
+            let label = text[..colon].trim();
+            if label.is_empty() || label.contains(char::is_whitespace) {
+                break;
+            }
+            match parse_value(label) {
+                Some(Value::Num(_)) => {}
+                Some(Value::Label(name)) => {
+                    if labels.insert(name.clone(), cells.len()).is_some() {
+                        return Err(AsmError::DuplicateLabel { line, label: name });
+                    }
+                }
+                None => break,
+            }
+            text = text[colon + 1..].trim();
+        }
+        if text.is_empty() {
+            continue;
+        }
+
+        let (head, rest) = match text.find(char::is_whitespace) {
+            Some(i) => (&text[..i], text[i..].trim()),
+            None => (text, ""),
+        };
+        let operands = if rest.is_empty() {
+            vec![]
+        } else {
+            rest.split(',').map(str::trim).collect::<Vec<_>>()
+        };
+
+        if head.eq_ignore_ascii_case(".data") {
+            for op in operands {
+                let value = parse_value(op).ok_or_else(|| AsmError::InvalidOperand {
+                    line,
+                    operand: op.to_string(),
+                })?;
+                cells.push((value, line));
+            }
+            continue;
+        }
+
+        let opcode = parse_mnemonic(head).ok_or_else(|| AsmError::UnknownMnemonic {
+            line,
+            mnemonic: head.to_string(),
+        })?;
+        if operands.len() != opcode.arity() {
+            return Err(AsmError::OperandCount {
+                line,
+                expected: opcode.arity(),
+                found: operands.len(),
+            });
+        }
+        let mut params = vec![];
+        let mut modes = 0;
+        for (i, op) in operands.iter().enumerate() {
+            let (mode, value) = parse_operand(op).ok_or_else(|| AsmError::InvalidOperand {
+                line,
+                operand: op.to_string(),
+            })?;
+            if mode == AddressingMode::Immediate && writes_last(opcode) && i + 1 == operands.len() {
+                return Err(AsmError::ImmediateWrite { line });
+            }
+            modes += mode.code() * 10i64.pow(i as u32);
+            params.push((value, line));
+        }
+        cells.push((Value::Num(modes * 100 + opcode.code()), line));
+        cells.extend(params);
+    }
+
+    cells
+        .into_iter()
+        .map(|(value, line)| match value {
+            Value::Num(n) => Ok(n),
+            Value::Label(label) => match labels.get(&label) {
+                Some(addr) => Ok(*addr as LargeNumber),
+                None => Err(AsmError::UndefinedLabel { line, label }),
+            },
+        })
+        .collect()
+}
+
+#[test]
+fn test_assemble_labels() {
+    let src = "
+        ; count down from the input
+        start:  STO [n]
+        loop:   LOAD [n]
+                ADD [n], #-1, [n]
+                JNZ [n], #loop
+                HLT
+        n:      .data 0
+    ";
+    let prog = assemble(src).unwrap();
+    assert_eq!(
+        prog,
+        vec![3, 12, 4, 12, 1001, 12, -1, 12, 1005, 12, 2, 99, 0]
+    );
+
+    let mut c = super::IntCodeComputer::with_io(prog, super::QueueIo::new(vec![3]));
+    c.run().unwrap();
+    assert_eq!(c.io.output, vec![3, 2, 1]);
+}
+
+#[test]
+fn test_listing_round_trip() {
+    let prog = super::parse_program(include_str!("../../inputs/input9.txt")).unwrap();
+    assert_eq!(assemble(&super::listing(&prog)).unwrap(), prog);
+
+    // the second mode digit of 1104 is unused and dropped
+    let prog = vec![1104, 5, 1199, 0];
+    let canonical = assemble(&super::listing(&prog)).unwrap();
+    assert_eq!(canonical, vec![104, 5, 99, 0]);
+    let output = |prog| {
+        let mut c = super::IntCodeComputer::with_io(prog, super::QueueIo::default());
+        c.run().unwrap();
+        c.io.output
+    };
+    assert_eq!(output(canonical), output(prog));
+}
+
+#[test]
+fn test_assemble_errors() {
+    assert_eq!(
+        assemble("ADD #1, #2, #3"),
+        Err(AsmError::ImmediateWrite { line: 1 })
+    );
+    assert_eq!(
+        assemble("HLT\nJMP #0"),
+        Err(AsmError::UnknownMnemonic {
+            line: 2,
+            mnemonic: "JMP".to_string()
+        })
+    );
+    assert_eq!(
+        assemble("JNZ #1, #end"),
+        Err(AsmError::UndefinedLabel {
+            line: 1,
+            label: "end".to_string()
+        })
+    );
+    assert_eq!(
+        assemble("LOAD #1, #2"),
+        Err(AsmError::OperandCount {
+            line: 1,
+            expected: 1,
+            found: 2
+        })
+    );
+}
@@ -0,0 +1,328 @@
+// Control-flow graph of an Intcode program.
+//
+// Basic blocks are found by following execution from address 0 like the
+// disassembler does, but only through jumps with immediate targets. A jump
+// to an address read from memory is a computed jump and has no successor
+// for its target. Writes with position-mode targets that land on a cell
+// execution reaches are reported as self-modifying code; relative-mode
+// writes depend on the relative base and are not checked.
+//
+use super::{decode_at, AddressingMode, Entry, LargeNumber, Opcode};
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Edge {
+    /// Falls through to the next instruction
+    Next,
+    /// Taken jump
+    Jump,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Block {
+    pub start: usize,
+    pub entries: Vec<Entry>,
+    /// Starts of blocks, or of invalid code, see `Finding::InvalidCode`
+    pub successors: Vec<(usize, Edge)>,
+}
+
+impl Block {
+    /// Address just past the last instruction
+    pub fn end(&self) -> usize {
+        self.entries
+            .last()
+            .map_or(self.start, |e| e.addr() + e.size())
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Finding {
+    /// A jump whose target is read from memory
+    ComputedJump { pc: usize },
+    /// A write into a cell that execution reaches
+    CodeWrite { pc: usize, target: usize },
+    /// Execution reaches a cell that is not a complete instruction
+    InvalidCode { pc: usize },
+}
+
+impl Finding {
+    pub fn pc(&self) -> usize {
+        match self {
+            Finding::ComputedJump { pc }
+            | Finding::CodeWrite { pc, .. }
+            | Finding::InvalidCode { pc } => *pc,
+        }
+    }
+}
+
+impl fmt::Display for Finding {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Finding::ComputedJump { pc } => write!(f, "{:04}: computed jump", pc),
+            Finding::CodeWrite { pc, target } => {
+                write!(f, "{:04}: writes code at {:04}", pc, target)
+            }
+            Finding::InvalidCode { pc } => write!(f, "{:04}: invalid instruction", pc),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ControlFlowGraph {
+    /// Basic blocks by start address
+    pub blocks: BTreeMap<usize, Block>,
+    /// In address order
+    pub findings: Vec<Finding>,
+}
+
+impl ControlFlowGraph {
+    pub fn build(prog: &[LargeNumber]) -> ControlFlowGraph {
+        let mut findings = vec![];
+        let mut code = BTreeMap::new();
+        let mut invalid = BTreeSet::new();
+        let mut pending = vec![0];
+        while let Some(addr) = pending.pop() {
+            if code.contains_key(&addr) || invalid.contains(&addr) {
+                continue;
+            }
+            let entry = match decode_at(prog, addr) {
+                Ok(entry) if addr + entry.size() <= prog.len() => entry,
+                _ => {
+                    invalid.insert(addr);
+                    findings.push(Finding::InvalidCode { pc: addr });
+                    continue;
+                }
+            };
+            let (successors, computed) = successors(&entry);
+            if computed {
+                findings.push(Finding::ComputedJump { pc: addr });
+            }
+            pending.extend(successors.iter().map(|(to, _)| *to));
+            code.insert(addr, (entry, successors));
+        }
+
+        // the entry, jump targets and whatever follows a branch start blocks
+        let mut leaders = BTreeSet::new();
+        leaders.insert(0);
+        for (entry, successors) in code.values() {
+            if !falls_through(entry, successors) {
+                leaders.extend(successors.iter().map(|(to, _)| *to));
+            }
+        }
+
+        let mut blocks = BTreeMap::new();
+        for start in leaders.iter().filter(|a| code.contains_key(a)) {
+            let mut block = Block {
+                start: *start,
+                entries: vec![],
+                successors: vec![],
+            };
+            let mut addr = *start;
+            loop {
+                let (entry, successors) = &code[&addr];
+                block.entries.push(entry.clone());
+                let next = addr + entry.size();
+                if !falls_through(entry, successors)
+                    || leaders.contains(&next)
+                    || !code.contains_key(&next)
+                {
+                    block.successors = successors.clone();
+                    break;
+                }
+                addr = next;
+            }
+            blocks.insert(*start, block);
+        }
+
+        let cells = code
+            .values()
+            .flat_map(|(e, _)| e.addr()..e.addr() + e.size())
+            .chain(findings.iter().map(Finding::pc))
+            .collect::<BTreeSet<_>>();
+        for (entry, _) in code.values() {
+            if let Some(target) = write_target(entry).filter(|t| cells.contains(t)) {
+                findings.push(Finding::CodeWrite {
+                    pc: entry.addr(),
+                    target,
+                });
+            }
+        }
+        findings.sort_by_key(Finding::pc);
+        ControlFlowGraph { blocks, findings }
+    }
+
+    /// Whether any instruction writes over code
+    pub fn is_self_modifying(&self) -> bool {
+        self.findings
+            .iter()
+            .any(|f| matches!(f, Finding::CodeWrite { .. }))
+    }
+
+    /// Renders the graph in Graphviz format, findings in red
+    pub fn dot(&self) -> String {
+        let mut out = String::from("digraph cfg {\n    node [shape=box, fontname=monospace];\n");
+        for block in self.blocks.values() {
+            let mut lines = block
+                .entries
+                .iter()
+                .map(|e| e.to_string())
+                .collect::<Vec<_>>();
+            let findings = self
+                .findings
+                .iter()
+                .filter(|f| (block.start..block.end()).contains(&f.pc()))
+                .map(|f| format!("! {}", f))
+                .collect::<Vec<_>>();
+            let color = if findings.is_empty() {
+                ""
+            } else {
+                ", color=red"
+            };
+            lines.extend(findings);
+            out += &format!(
+                "    b{} [label=\"{}\\l\"{}];\n",
+                block.start,
+                lines.join("\\l"),
+                color
+            );
+        }
+        for finding in &self.findings {
+            if let Finding::InvalidCode { pc } = finding {
+                out += &format!("    b{} [label=\"{}\\l\", color=red];\n", pc, finding);
+            }
+        }
+        for block in self.blocks.values() {
+            for (to, edge) in &block.successors {
+                let label = match edge {
+                    Edge::Next => "",
+                    Edge::Jump => " [label=jump]",
+                };
+                out += &format!("    b{} -> b{}{};\n", block.start, to, label);
+            }
+        }
+        out += "}\n";
+        out
+    }
+}
+
+/// Addresses execution can continue at and whether the jump is computed
+fn successors(entry: &Entry) -> (Vec<(usize, Edge)>, bool) {
+    let (ins, params) = match entry {
+        Entry::Code { ins, params, .. } => (ins, params),
+        Entry::Data { .. } => return (vec![], false),
+    };
+    let next = (entry.addr() + entry.size(), Edge::Next);
+    match ins.opcode {
+        Opcode::HLT => (vec![], false),
+        Opcode::JNZ | Opcode::JZ => {
+            // whether the jump is taken, when the condition is immediate
+            let cond = match ins.pm1 {
+                AddressingMode::Immediate => Some((params[0] != 0) == (ins.opcode == Opcode::JNZ)),
+                _ => None,
+            };
+            let mut out = vec![];
+            let computed = ins.pm2 != AddressingMode::Immediate;
+            if cond != Some(false) && !computed && params[1] >= 0 {
+                out.push((params[1] as usize, Edge::Jump));
+            }
+            if cond != Some(true) {
+                out.push(next);
+            }
+            (out, computed && cond != Some(false))
+        }
+        _ => (vec![next], false),
+    }
+}
+
+/// Whether the instruction only ever continues with the next one
+fn falls_through(entry: &Entry, successors: &[(usize, Edge)]) -> bool {
+    let jump = match entry {
+        Entry::Code { ins, .. } => matches!(ins.opcode, Opcode::JNZ | Opcode::JZ),
+        Entry::Data { .. } => false,
+    };
+    !jump && successors == [(entry.addr() + entry.size(), Edge::Next)]
+}
+
+/// Cell written by the instruction, when the address is in the program
+fn write_target(entry: &Entry) -> Option<usize> {
+    let (ins, params) = match entry {
+        Entry::Code { ins, params, .. } => (ins, params),
+        Entry::Data { .. } => return None,
+    };
+    let (mode, param) = match ins.opcode {
+        Opcode::STO => (ins.pm1, params[0]),
+        Opcode::ADD | Opcode::MUL | Opcode::LT | Opcode::EQ => (ins.pm3, params[2]),
+        _ => return None,
+    };
+    match mode {
+        AddressingMode::Position if param >= 0 => Some(param as usize),
+        _ => None,
+    }
+}
+
+#[test]
+fn test_basic_blocks() {
+    // the day 9 quine reads its own code through the relative base but
+    // never writes it
+    let quine = vec![
+        109, 1, 204, -1, 1001, 100, 1, 100, 1008, 100, 16, 101, 1006, 101, 0, 99,
+    ];
+    let cfg = ControlFlowGraph::build(&quine);
+    assert_eq!(cfg.blocks.keys().copied().collect::<Vec<_>>(), vec![0, 15]);
+    assert_eq!(
+        cfg.blocks[&0].successors,
+        vec![(0, Edge::Jump), (15, Edge::Next)]
+    );
+    assert_eq!(cfg.blocks[&0].end(), 15);
+    assert!(cfg.findings.is_empty());
+
+    // jump to the input, and a jump that is always taken
+    let prog = vec![3, 8, 5, 8, 8, 1105, 1, 0, 99];
+    let cfg = ControlFlowGraph::build(&prog);
+    assert_eq!(cfg.findings, vec![Finding::ComputedJump { pc: 2 }]);
+    assert_eq!(
+        cfg.dot(),
+        "digraph cfg {\n    \
+         node [shape=box, fontname=monospace];\n    \
+         b0 [label=\"0000: STO [8]\\l0002: JNZ [8], [8]\\l\
+         ! 0002: computed jump\\l\", color=red];\n    \
+         b5 [label=\"0005: JNZ #1, #0\\l\"];\n    \
+         b0 -> b5;\n    \
+         b5 -> b0 [label=jump];\n\
+         }\n"
+    );
+}
+
+#[test]
+fn test_self_modifying() {
+    // the day 5 example turns the cell after its `MUL` into `HLT`
+    let cfg = ControlFlowGraph::build(&[1002, 4, 3, 4, 33]);
+    assert_eq!(
+        cfg.findings,
+        vec![
+            Finding::CodeWrite { pc: 0, target: 4 },
+            Finding::InvalidCode { pc: 4 },
+        ]
+    );
+    assert_eq!(cfg.blocks[&0].successors, vec![(4, Edge::Next)]);
+    assert!(cfg.is_self_modifying());
+
+    // the puzzle input adds the system ID to the opcode of its sixth cell
+    let prog = super::parse_program(include_str!("../../inputs/input5.txt")).unwrap();
+    let cfg = ControlFlowGraph::build(&prog);
+    assert!(cfg
+        .findings
+        .contains(&Finding::CodeWrite { pc: 2, target: 6 }));
+    assert!(!ControlFlowGraph::build(&[1101, 1, 2, 5, 99, 0]).is_self_modifying());
+
+    // 9 is reached three times, with 10 in between
+    let cfg = ControlFlowGraph::build(&[1006, 30, 9, 1006, 30, 10, 1105, 1, 9, 98, 97]);
+    assert_eq!(
+        cfg.findings,
+        vec![
+            Finding::InvalidCode { pc: 9 },
+            Finding::InvalidCode { pc: 10 }
+        ]
+    );
+}
@@ -0,0 +1,123 @@
+// Breakpoints and watchpoints on top of a single-stepped computer.
+//
+use super::{IntCodeComputer, IntcodeError, LargeNumber, Memory, QueueIo, StepOutcome};
+use std::collections::{BTreeMap, BTreeSet};
+
+/// Why execution returned control to the debugger
+#[derive(Debug, PartialEq)]
+pub enum Stop {
+    Stepped,
+    Breakpoint(usize),
+    Watchpoint {
+        addr: usize,
+        old: LargeNumber,
+        new: LargeNumber,
+    },
+    NeedsInput,
+    Halted,
+}
+
+pub struct Debugger {
+    pub computer: IntCodeComputer<QueueIo>,
+    breakpoints: BTreeSet<usize>,
+    watchpoints: BTreeMap<usize, LargeNumber>,
+}
+
+impl Debugger {
+    pub fn new(mem: Memory) -> Debugger {
+        Debugger {
+            computer: IntCodeComputer::with_io(mem, QueueIo::default()),
+            breakpoints: BTreeSet::new(),
+            watchpoints: BTreeMap::new(),
+        }
+    }
+
+    pub fn breakpoints(&self) -> &BTreeSet<usize> {
+        &self.breakpoints
+    }
+
+    pub fn watchpoints(&self) -> &BTreeMap<usize, LargeNumber> {
+        &self.watchpoints
+    }
+
+    pub fn add_breakpoint(&mut self, addr: usize) {
+        self.breakpoints.insert(addr);
+    }
+
+    pub fn remove_breakpoint(&mut self, addr: usize) -> bool {
+        self.breakpoints.remove(&addr)
+    }
+
+    pub fn add_watchpoint(&mut self, addr: usize) {
+        let value = self.cell(addr);
+        self.watchpoints.insert(addr, value);
+    }
+
+    pub fn remove_watchpoint(&mut self, addr: usize) -> bool {
+        self.watchpoints.remove(&addr).is_some()
+    }
+
+    pub fn cell(&self, addr: usize) -> LargeNumber {
+        self.computer.mem.get(addr).copied().unwrap_or(0)
+    }
+
+    /// Executes one instruction, reporting the first watched cell it changed
+    pub fn step(&mut self) -> Result<Stop, IntcodeError> {
+        match self.computer.step()? {
+            StepOutcome::NeedsInput => return Ok(Stop::NeedsInput),
+            StepOutcome::Halted => return Ok(Stop::Halted),
+            _ => {}
+        }
+        let mem = &self.computer.mem;
+        for (addr, last) in self.watchpoints.iter_mut() {
+            let new = mem.get(*addr).copied().unwrap_or(0);
+            if new != *last {
+                let old = std::mem::replace(last, new);
+                return Ok(Stop::Watchpoint {
+                    addr: *addr,
+                    old,
+                    new,
+                });
+            }
+        }
+        Ok(Stop::Stepped)
+    }
+
+    /// Steps until a breakpoint, watchpoint, halt or missing input
+    pub fn cont(&mut self) -> Result<Stop, IntcodeError> {
+        loop {
+            match self.step()? {
+                Stop::Stepped if self.breakpoints.contains(&self.computer.pc) => {
+                    return Ok(Stop::Breakpoint(self.computer.pc))
+                }
+                Stop::Stepped => {}
+                stop => return Ok(stop),
+            }
+        }
+    }
+}
+
+#[test]
+fn test_breakpoints_and_watchpoints() {
+    // counts [9] down from 3 to 0
+    let mut dbg = Debugger::new(vec![1001, 9, -1, 9, 1005, 9, 0, 99, 0, 3]);
+    dbg.add_breakpoint(4);
+    assert_eq!(dbg.cont(), Ok(Stop::Breakpoint(4)));
+    assert_eq!(dbg.cell(9), 2);
+
+    dbg.remove_breakpoint(4);
+    dbg.add_watchpoint(9);
+    assert_eq!(
+        dbg.cont(),
+        Ok(Stop::Watchpoint {
+            addr: 9,
+            old: 2,
+            new: 1
+        })
+    );
+    assert_eq!(dbg.computer.pc, 4);
+
+    dbg.remove_watchpoint(9);
+    assert_eq!(dbg.cont(), Ok(Stop::Halted));
+    assert_eq!(dbg.cell(9), 0);
+}
@@ -0,0 +1,183 @@
+// Disassembler producing readable listings of Intcode programs.
+//
+// Code is found by following execution from address 0: straight-line
+// flow, jumps with immediate targets and position-mode targets as they
+// are in the loaded program. Cells never reached are listed as data.
+//
+use super::{AddressingMode, Instruction, IntcodeError, LargeNumber, Opcode};
+use std::collections::BTreeMap;
+use std::fmt;
+
+/// Data cells grouped on a single listing line
+const DATA_PER_LINE: usize = 8;
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Entry {
+    Code {
+        addr: usize,
+        ins: Instruction,
+        params: Vec<LargeNumber>,
+    },
+    Data {
+        addr: usize,
+        values: Vec<LargeNumber>,
+    },
+}
+
+impl Entry {
+    pub fn addr(&self) -> usize {
+        match self {
+            Entry::Code { addr, .. } | Entry::Data { addr, .. } => *addr,
+        }
+    }
+
+    /// Number of memory cells covered by the entry
+    pub fn size(&self) -> usize {
+        match self {
+            Entry::Code { params, .. } => params.len() + 1,
+            Entry::Data { values, .. } => values.len(),
+        }
+    }
+}
+
+impl fmt::Display for Entry {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Entry::Code { addr, ins, params } => {
+                write!(f, "{:04}: {:?}", addr, ins.opcode)?;
+                for (i, (mode, param)) in ins.modes().iter().zip(params).enumerate() {
+                    let sep = if i == 0 { " " } else { ", " };
+                    write!(f, "{}{}", sep, operand(*mode, *param))?;
+                }
+                Ok(())
+            }
+            Entry::Data { addr, values } => {
+                let values = values.iter().map(|v| v.to_string()).collect::<Vec<_>>();
+                write!(f, "{:04}: .data {}", addr, values.join(", "))
+            }
+        }
+    }
+}
+
+fn operand(mode: AddressingMode, param: LargeNumber) -> String {
+    match mode {
+        AddressingMode::Immediate => format!("#{}", param),
+        AddressingMode::Position => format!("[{}]", param),
+        AddressingMode::Relative => format!("[rb{:+}]", param),
+    }
+}
+
+/// Decodes the instruction at `addr`, reading missing parameters as zero
+pub fn decode_at(mem: &[LargeNumber], addr: usize) -> Result<Entry, IntcodeError> {
+    let cell = |a: usize| mem.get(a).copied().unwrap_or(0);
+    let ins = Instruction::decode(cell(addr), addr)?;
+    let params = (1..=ins.opcode.arity()).map(|i| cell(addr + i)).collect();
+    Ok(Entry::Code { addr, ins, params })
+}
+
+/// Maps instruction addresses reachable from 0 to their decoded entries
+fn trace_code(prog: &[LargeNumber]) -> BTreeMap<usize, Entry> {
+    let mut code = BTreeMap::new();
+    let mut pending = vec![0];
+    while let Some(addr) = pending.pop() {
+        if code.contains_key(&addr) {
+            continue;
+        }
+        let entry = match decode_at(prog, addr) {
+            Ok(entry) if addr + entry.size() <= prog.len() => entry,
+            _ => continue,
+        };
+        if let Entry::Code { ins, params, .. } = &entry {
+            let next = addr + entry.size();
+            match ins.opcode {
+                Opcode::HLT => {}
+                Opcode::JNZ | Opcode::JZ => {
+                    let cond = match ins.pm1 {
+                        AddressingMode::Immediate => Some(params[0] != 0),
+                        _ => None,
+                    };
+                    let taken = if ins.opcode == Opcode::JNZ {
+                        cond
+                    } else {
+                        cond.map(|c| !c)
+                    };
+                    let target = match ins.pm2 {
+                        AddressingMode::Immediate => Some(params[1]),
+                        AddressingMode::Position if params[1] >= 0 => {
+                            prog.get(params[1] as usize).copied()
+                        }
+                        _ => None,
+                    };
+                    if taken != Some(false) {
+                        if let Some(t) = target.filter(|t| *t >= 0) {
+                            pending.push(t as usize);
+                        }
+                    }
+                    if taken != Some(true) {
+                        pending.push(next);
+                    }
+                }
+                _ => pending.push(next),
+            }
+        }
+        code.insert(addr, entry);
+    }
+    code
+}
+
+/// Splits a program into code and data entries in address order
+pub fn disassemble(prog: &[LargeNumber]) -> Vec<Entry> {
+    let code = trace_code(prog);
+    let mut entries = vec![];
+    let mut addr = 0;
+    while addr < prog.len() {
+        if let Some(entry) = code.get(&addr) {
+            addr += entry.size();
+            entries.push(entry.clone());
+            continue;
+        }
+        let start = addr;
+        while addr < prog.len() && !code.contains_key(&addr) && addr - start < DATA_PER_LINE {
+            addr += 1;
+        }
+        entries.push(Entry::Data {
+            addr: start,
+            values: prog[start..addr].to_vec(),
+        });
+    }
+    entries
+}
+
+/// Renders the disassembly one entry per line
+pub fn listing(prog: &[LargeNumber]) -> String {
+    disassemble(prog)
+        .iter()
+        .map(|e| e.to_string())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+#[test]
+fn test_operand_annotations() {
+    assert_eq!(
+        decode_at(&[1201, 3, 5, 120], 0).unwrap().to_string(),
+        "0000: ADD [rb+3], #5, [120]"
+    );
+    assert_eq!(
+        decode_at(&[0, 0, 204, -1], 2).unwrap().to_string(),
+        "0002: LOAD [rb-1]"
+    );
+}
+
+#[test]
+fn test_listing_marks_data() {
+    let prog = vec![1105, 1, 4, 42, 104, 5, 99, 7, 8];
+    assert_eq!(
+        listing(&prog),
+        "0000: JNZ #1, #4\n\
+         0003: .data 42\n\
+         0004: LOAD #5\n\
+         0006: HLT\n\
+         0007: .data 7, 8"
+    );
+}
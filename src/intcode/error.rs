@@ -0,0 +1,60 @@
+// Errors raised while executing an Intcode program.
+//
+use super::{IoError, LargeNumber, Limit};
+use std::fmt;
+
+/// Every variant carries the address of the failing instruction
+#[derive(Clone, Debug, PartialEq)]
+pub enum IntcodeError {
+    UnknownOpcode { pc: usize, opcode: LargeNumber },
+    InvalidMode { pc: usize, mode: LargeNumber },
+    ImmediateWrite { pc: usize },
+    NegativeAddress { pc: usize, addr: LargeNumber },
+    InputExhausted { pc: usize },
+    LimitExceeded { pc: usize, limit: Limit },
+    Overflow { pc: usize },
+    OutOfMemory { pc: usize, addr: usize },
+    Io { pc: usize, error: IoError },
+}
+
+impl IntcodeError {
+    pub fn pc(&self) -> usize {
+        match *self {
+            Self::UnknownOpcode { pc, .. }
+            | Self::InvalidMode { pc, .. }
+            | Self::ImmediateWrite { pc }
+            | Self::NegativeAddress { pc, .. }
+            | Self::InputExhausted { pc }
+            | Self::LimitExceeded { pc, .. }
+            | Self::Overflow { pc }
+            | Self::OutOfMemory { pc, .. }
+            | Self::Io { pc, .. } => pc,
+        }
+    }
+}
+
+impl fmt::Display for IntcodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::UnknownOpcode { pc, opcode } => {
+                write!(f, "unknown opcode {} at {}", opcode, pc)
+            }
+            Self::InvalidMode { pc, mode } => {
+                write!(f, "invalid addressing mode {} at {}", mode, pc)
+            }
+            Self::ImmediateWrite { pc } => write!(f, "write in immediate mode at {}", pc),
+            Self::NegativeAddress { pc, addr } => {
+                write!(f, "negative address {} at {}", addr, pc)
+            }
+            Self::InputExhausted { pc } => write!(f, "input exhausted at {}", pc),
+            Self::LimitExceeded { pc, limit } => write!(f, "{} exceeded at {}", limit, pc),
+            Self::Overflow { pc } => write!(f, "arithmetic overflow at {}", pc),
+            Self::OutOfMemory { pc, addr } => {
+                write!(f, "cannot allocate memory for address {} at {}", addr, pc)
+            }
+            Self::Io { pc, error } => write!(f, "{} at {}", error, pc),
+        }
+    }
+}
+
+impl std::error::Error for IntcodeError {}
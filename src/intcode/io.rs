@@ -0,0 +1,221 @@
+// Input sources and output sinks for the Intcode computer.
+//
+use super::LargeNumber;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::fmt;
+use std::io::{self, BufRead, Write};
+use std::sync::mpsc;
+
+/// A read or write that failed, rather than input that is not there yet
+#[derive(Clone, Debug, PartialEq)]
+pub struct IoError(pub String);
+
+impl fmt::Display for IoError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl Error for IoError {}
+
+impl From<io::Error> for IoError {
+    fn from(e: io::Error) -> IoError {
+        IoError(e.to_string())
+    }
+}
+
+/// Where `STO` reads its values from and where `LOAD` writes them to
+pub trait IntcodeIo {
+    /// Returns `Ok(None)` when no input is available
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError>;
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError>;
+}
+
+impl<T: IntcodeIo + ?Sized> IntcodeIo for Box<T> {
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        (**self).input()
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        (**self).output(value)
+    }
+}
+
+/// Prompts on stdin and prints outputs on stdout, input runs out at the
+/// end of stdin
+#[derive(Clone, Copy, Debug, Default)]
+pub struct StdIo;
+
+impl IntcodeIo for StdIo {
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        let mut stdout = io::stdout();
+        write!(stdout, "Enter Input: ")?;
+        stdout.flush()?;
+        read_value(&mut io::stdin().lock())
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        writeln!(io::stdout(), "{}", value)?;
+        Ok(())
+    }
+}
+
+/// The number on the next line, `None` at the end of the input
+fn read_value<R: BufRead>(reader: &mut R) -> Result<Option<LargeNumber>, IoError> {
+    let mut line = String::new();
+    if reader.read_line(&mut line)? == 0 {
+        return Ok(None);
+    }
+    match line.trim().parse() {
+        Ok(value) => Ok(Some(value)),
+        Err(_) => Err(IoError(format!("invalid input {:?}", line.trim()))),
+    }
+}
+
+/// Reads from and writes to in-memory queues
+#[derive(Clone, Debug, Default)]
+pub struct QueueIo {
+    pub input: VecDeque<LargeNumber>,
+    pub output: VecDeque<LargeNumber>,
+}
+
+impl QueueIo {
+    pub fn new<I: IntoIterator<Item = LargeNumber>>(inputs: I) -> QueueIo {
+        QueueIo {
+            input: inputs.into_iter().collect(),
+            output: VecDeque::new(),
+        }
+    }
+}
+
+impl IntcodeIo for QueueIo {
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        Ok(self.input.pop_front())
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        self.output.push_back(value);
+        Ok(())
+    }
+}
+
+/// Pulls inputs from an iterator and collects outputs
+#[derive(Debug)]
+pub struct IterIo<I> {
+    inputs: I,
+    pub outputs: Vec<LargeNumber>,
+}
+
+impl<I: Iterator<Item = LargeNumber>> IterIo<I> {
+    pub fn new<T: IntoIterator<IntoIter = I>>(inputs: T) -> IterIo<I> {
+        IterIo {
+            inputs: inputs.into_iter(),
+            outputs: vec![],
+        }
+    }
+}
+
+impl<I: Iterator<Item = LargeNumber>> IntcodeIo for IterIo<I> {
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        Ok(self.inputs.next())
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        self.outputs.push(value);
+        Ok(())
+    }
+}
+
+/// Blocks on an inbox and sends outputs to an outbox, input runs out once
+/// every sender is gone
+#[derive(Debug)]
+pub struct ChannelIo {
+    pub inbox: mpsc::Receiver<LargeNumber>,
+    pub outbox: mpsc::Sender<LargeNumber>,
+}
+
+impl ChannelIo {
+    pub fn new(inbox: mpsc::Receiver<LargeNumber>, outbox: mpsc::Sender<LargeNumber>) -> ChannelIo {
+        ChannelIo { inbox, outbox }
+    }
+}
+
+impl IntcodeIo for ChannelIo {
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        Ok(self.inbox.recv().ok())
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        self.outbox
+            .send(value)
+            .map_err(|_| IoError(format!("outbox closed, {} was not sent", value)))
+    }
+}
+
+/// Delegates to a pair of closures
+pub struct CallbackIo<I, O> {
+    input: I,
+    output: O,
+}
+
+impl<I, O> CallbackIo<I, O>
+where
+    I: FnMut() -> Option<LargeNumber>,
+    O: FnMut(LargeNumber),
+{
+    pub fn new(input: I, output: O) -> CallbackIo<I, O> {
+        CallbackIo { input, output }
+    }
+}
+
+impl<I, O> IntcodeIo for CallbackIo<I, O>
+where
+    I: FnMut() -> Option<LargeNumber>,
+    O: FnMut(LargeNumber),
+{
+    fn input(&mut self) -> Result<Option<LargeNumber>, IoError> {
+        Ok((self.input)())
+    }
+
+    fn output(&mut self, value: LargeNumber) -> Result<(), IoError> {
+        (self.output)(value);
+        Ok(())
+    }
+}
+
+#[test]
+fn test_queue_io() {
+    let mut io = QueueIo::new(vec![1, 2]);
+    assert_eq!(io.input(), Ok(Some(1)));
+    io.output(7).unwrap();
+    assert_eq!(io.input(), Ok(Some(2)));
+    assert_eq!(io.input(), Ok(None));
+    assert_eq!(io.output, vec![7]);
+
+    let mut lines = "12\n x\n".as_bytes();
+    assert_eq!(read_value(&mut lines), Ok(Some(12)));
+    assert_eq!(
+        read_value(&mut lines),
+        Err(IoError("invalid input \"x\"".to_string()))
+    );
+    assert_eq!(read_value(&mut lines), Ok(None));
+}
+
+#[test]
+fn test_channel_io() {
+    let (tx, inbox) = mpsc::channel();
+    let (outbox, rx) = mpsc::channel();
+    let mut io = ChannelIo::new(inbox, outbox);
+    tx.send(5).unwrap();
+    assert_eq!(io.input(), Ok(Some(5)));
+    io.output(6).unwrap();
+    assert_eq!(rx.recv(), Ok(6));
+    drop(tx);
+    assert_eq!(io.input(), Ok(None));
+    drop(rx);
+    assert_eq!(
+        io.output(7),
+        Err(IoError("outbox closed, 7 was not sent".to_string()))
+    );
+}
@@ -0,0 +1,43 @@
+// Bounds on what an Intcode program may do, for running untrusted code.
+//
+// A computer checks its limits before every instruction. Once one would
+// be exceeded it stops in `ComputerState::Stopped`, failing with
+// `LimitExceeded`, and its pc stays on the instruction that was not run.
+// Raising the limit lets it continue from there.
+//
+use std::fmt;
+use std::time::Duration;
+
+/// The bound a computer stopped at
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Limit {
+    /// Instructions executed
+    Steps(usize),
+    /// Cells that writes may address, from 0
+    Memory(usize),
+    /// Time since the limit was set
+    Time(Duration),
+}
+
+impl fmt::Display for Limit {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Limit::Steps(n) => write!(f, "step limit of {}", n),
+            Limit::Memory(n) => write!(f, "memory limit of {} cells", n),
+            Limit::Time(d) => write!(f, "time limit of {:?}", d),
+        }
+    }
+}
+
+/// Unset fields are unbounded
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct Limits {
+    pub steps: Option<usize>,
+    pub memory: Option<usize>,
+    /// Checked every `TIME_CHECK_INTERVAL` steps, and not while blocked on
+    /// input
+    pub time: Option<Duration>,
+}
+
+/// Steps between checks of the clock
+pub const TIME_CHECK_INTERVAL: usize = 1024;
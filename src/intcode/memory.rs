@@ -0,0 +1,158 @@
+// Memory backends for the Intcode computer.
+//
+// `Vec<LargeNumber>` is the dense fast path and grows with zeros up to
+// the highest written address. `PagedMemory` only allocates the pages
+// that are written to, so programs can use addresses like 10^12.
+//
+use super::LargeNumber;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::mem::size_of;
+
+/// Cells per page of `PagedMemory`
+pub const PAGE_SIZE: usize = 1024;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+pub struct MemoryStats {
+    /// Cells backed by storage
+    pub allocated: usize,
+    /// Approximate heap usage of the cells
+    pub bytes: usize,
+    pub pages: usize,
+    /// Highest address backed by storage
+    pub max_addr: Option<usize>,
+}
+
+/// A write the backend could not allocate room for
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct OutOfMemory {
+    pub addr: usize,
+}
+
+impl fmt::Display for OutOfMemory {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "cannot allocate memory for address {}", self.addr)
+    }
+}
+
+impl Error for OutOfMemory {}
+
+pub trait MemoryBackend {
+    /// Cells that were never written read as zero
+    fn load(&self, addr: usize) -> LargeNumber;
+    fn store(&mut self, addr: usize, value: LargeNumber) -> Result<(), OutOfMemory>;
+    fn stats(&self) -> MemoryStats;
+    /// Allocated cells as `(start address, cells)` runs in address order
+    fn segments(&self) -> Vec<(usize, Vec<LargeNumber>)>;
+}
+
+impl MemoryBackend for Vec<LargeNumber> {
+    fn load(&self, addr: usize) -> LargeNumber {
+        self.get(addr).copied().unwrap_or(0)
+    }
+
+    fn store(&mut self, addr: usize, value: LargeNumber) -> Result<(), OutOfMemory> {
+        if addr >= self.len() {
+            let len = addr.checked_add(1).ok_or(OutOfMemory { addr })?;
+            self.try_reserve(len - self.len())
+                .map_err(|_| OutOfMemory { addr })?;
+            self.resize(len, 0);
+        }
+        self[addr] = value;
+        Ok(())
+    }
+
+    fn stats(&self) -> MemoryStats {
+        MemoryStats {
+            allocated: self.len(),
+            bytes: self.capacity() * size_of::<LargeNumber>(),
+            pages: 0,
+            max_addr: self.len().checked_sub(1),
+        }
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<LargeNumber>)> {
+        vec![(0, self.clone())]
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PagedMemory {
+    pages: HashMap<usize, Box<[LargeNumber]>>,
+}
+
+impl PagedMemory {
+    pub fn new(prog: &[LargeNumber]) -> PagedMemory {
+        let mut mem = PagedMemory::default();
+        for (addr, value) in prog.iter().enumerate() {
+            mem.set(addr, *value);
+        }
+        mem
+    }
+
+    fn set(&mut self, addr: usize, value: LargeNumber) {
+        let page = self
+            .pages
+            .entry(addr / PAGE_SIZE)
+            .or_insert_with(|| vec![0; PAGE_SIZE].into_boxed_slice());
+        page[addr % PAGE_SIZE] = value;
+    }
+}
+
+impl MemoryBackend for PagedMemory {
+    fn load(&self, addr: usize) -> LargeNumber {
+        self.pages
+            .get(&(addr / PAGE_SIZE))
+            .map_or(0, |page| page[addr % PAGE_SIZE])
+    }
+
+    /// Pages are small, so this never runs out of memory
+    fn store(&mut self, addr: usize, value: LargeNumber) -> Result<(), OutOfMemory> {
+        self.set(addr, value);
+        Ok(())
+    }
+
+    fn stats(&self) -> MemoryStats {
+        let pages = self.pages.len();
+        MemoryStats {
+            allocated: pages * PAGE_SIZE,
+            bytes: pages * PAGE_SIZE * size_of::<LargeNumber>(),
+            pages,
+            max_addr: self.pages.keys().max().map(|p| (p + 1) * PAGE_SIZE - 1),
+        }
+    }
+
+    fn segments(&self) -> Vec<(usize, Vec<LargeNumber>)> {
+        let mut segments = self
+            .pages
+            .iter()
+            .map(|(p, page)| (p * PAGE_SIZE, page.to_vec()))
+            .collect::<Vec<_>>();
+        segments.sort_by_key(|s| s.0);
+        segments
+    }
+}
+
+#[test]
+fn test_paged_memory() {
+    let mut mem = PagedMemory::new(&[1, 2, 3]);
+    assert_eq!(mem.load(2), 3);
+    assert_eq!(mem.load(5000), 0);
+    mem.store(1_000_000_000_000, 7).unwrap();
+    assert_eq!(mem.load(1_000_000_000_000), 7);
+
+    let stats = mem.stats();
+    assert_eq!(stats.pages, 2);
+    assert_eq!(stats.allocated, 2 * PAGE_SIZE);
+    assert!(stats.max_addr.unwrap() >= 1_000_000_000_000);
+
+    let mut dense = vec![1, 2, 3];
+    assert_eq!(
+        dense.store(usize::MAX / 2, 4),
+        Err(OutOfMemory {
+            addr: usize::MAX / 2
+        })
+    );
+    assert_eq!(dense, vec![1, 2, 3]);
+}
@@ -0,0 +1,499 @@
+// Networks of Intcode computers wired by their outputs.
+//
+// The network runs in rounds: every machine that can make progress runs
+// until it halts or blocks on input, then the outputs of the round are
+// delivered. Machines only block on their own input queue, so the result
+// does not depend on whether a round runs on one thread or on several.
+//
+use super::{ComputerState, IntCodeComputer, IntcodeError, LargeNumber, QueueIo, StepOutcome};
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::sync::mpsc;
+use std::thread::Scope;
+
+pub type Machine = IntCodeComputer<QueueIo>;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Topology {
+    /// Every machine feeds the next, and the last feeds the first
+    Ring,
+    /// Every machine feeds the next
+    Chain,
+    /// The hub feeds every other machine and they all feed the hub
+    Star { hub: usize },
+    /// Every machine feeds every other machine
+    FullyConnected,
+    /// Outputs are packets of `size` values whose first value is the
+    /// address of the receiving machine; the rest is delivered to it
+    Bus { size: usize },
+}
+
+/// A machine id past the end of the network
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct UnknownMachine {
+    pub id: usize,
+    pub machines: usize,
+}
+
+impl fmt::Display for UnknownMachine {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "no machine {} in a network of {}",
+            self.id, self.machines
+        )
+    }
+}
+
+impl Error for UnknownMachine {}
+
+/// A value handed to a machine's input
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delivery {
+    /// Position among every delivery of the network, from 0
+    pub seq: usize,
+    /// Round the value was output in, from 0. Values passed to `send`
+    /// belong to the next round to run
+    pub round: usize,
+    /// `None` for values passed to `send`
+    pub from: Option<usize>,
+    pub to: usize,
+    pub value: LargeNumber,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum MachineStatus {
+    Halted,
+    WaitingForInput,
+    Failed(IntcodeError),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct MachineReport {
+    pub status: MachineStatus,
+    pub pc: usize,
+    pub steps: usize,
+    pub outputs: Vec<LargeNumber>,
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct NetworkReport {
+    pub rounds: usize,
+    /// Set when the network stopped with machines still waiting for input
+    pub deadlocked: bool,
+    pub machines: Vec<MachineReport>,
+}
+
+impl NetworkReport {
+    /// Ids of the machines left waiting for input
+    pub fn waiting(&self) -> Vec<usize> {
+        self.machines
+            .iter()
+            .enumerate()
+            .filter(|(_, m)| m.status == MachineStatus::WaitingForInput)
+            .map(|(id, _)| id)
+            .collect()
+    }
+}
+
+struct Node {
+    machine: Machine,
+    edges: Vec<usize>,
+    /// Outputs already delivered to other machines
+    delivered: usize,
+    error: Option<IntcodeError>,
+}
+
+impl Node {
+    fn runnable(&self) -> bool {
+        self.error.is_none() && self.machine.state != ComputerState::Halted
+    }
+
+    fn run(&mut self) -> bool {
+        let steps = self.machine.steps();
+        match self.machine.run_until(|_| false) {
+            Ok(StepOutcome::Halted) => true,
+            Ok(_) => self.machine.steps() != steps,
+            Err(e) => {
+                self.error = Some(e);
+                true
+            }
+        }
+    }
+}
+
+/// Runs every node, even after one has made progress
+fn run_all<'a, I: Iterator<Item = &'a mut Node>>(nodes: I) -> bool {
+    nodes.map(|n| n.run()).filter(|p| *p).count() > 0
+}
+
+/// Nodes lent to a worker, with their ids
+type Batch = Vec<(usize, Node)>;
+
+/// Worker threads that run batches of nodes
+struct Pool {
+    jobs: Vec<mpsc::Sender<Batch>>,
+    /// Batches back from each worker, and whether they made progress
+    done: Vec<mpsc::Receiver<(Batch, bool)>>,
+}
+
+impl Pool {
+    fn start<'scope, 'env>(s: &'scope Scope<'scope, 'env>, workers: usize) -> Pool {
+        let (jobs, done) = (0..workers)
+            .map(|_| {
+                let (jobs, inbox) = mpsc::channel::<Batch>();
+                let (outbox, done) = mpsc::channel();
+                s.spawn(move || {
+                    // ends once the pool is dropped
+                    for mut batch in inbox {
+                        let progressed = run_all(batch.iter_mut().map(|(_, n)| n));
+                        if outbox.send((batch, progressed)).is_err() {
+                            break;
+                        }
+                    }
+                });
+                (jobs, done)
+            })
+            .unzip();
+        Pool { jobs, done }
+    }
+
+    /// Spreads the runnable nodes over the workers and puts them back
+    /// in place once every worker is done
+    fn round(&self, nodes: &mut Vec<Node>) -> bool {
+        let (runnable, mut idle): (Batch, Batch) = std::mem::take(nodes)
+            .into_iter()
+            .enumerate()
+            .partition(|(_, n)| n.runnable());
+        let chunk = (runnable.len() + self.jobs.len() - 1) / self.jobs.len();
+        let mut runnable = runnable.into_iter();
+        let mut busy = vec![];
+        for (jobs, done) in self.jobs.iter().zip(&self.done) {
+            let batch = runnable.by_ref().take(chunk.max(1)).collect::<Batch>();
+            if batch.is_empty() {
+                break;
+            }
+            jobs.send(batch).expect("network worker stopped");
+            busy.push(done);
+        }
+        let mut progressed = false;
+        for done in busy {
+            let (batch, p) = done.recv().expect("network worker panicked");
+            progressed |= p;
+            idle.extend(batch);
+        }
+        idle.sort_by_key(|(id, _)| *id);
+        *nodes = idle.into_iter().map(|(_, n)| n).collect();
+        progressed
+    }
+}
+
+pub struct Network {
+    nodes: Vec<Node>,
+    packet_size: Option<usize>,
+    /// Rounds run so far
+    rounds: usize,
+    /// Every value delivered so far, in delivery order
+    pub deliveries: Vec<Delivery>,
+    /// Bus packets addressed to machines that do not exist
+    pub unrouted: Vec<Vec<LargeNumber>>,
+}
+
+impl Network {
+    /// Machines that are not connected to each other yet
+    pub fn new(machines: Vec<Machine>) -> Network {
+        Network {
+            nodes: machines
+                .into_iter()
+                .map(|machine| Node {
+                    machine,
+                    edges: vec![],
+                    delivered: 0,
+                    error: None,
+                })
+                .collect(),
+            packet_size: None,
+            rounds: 0,
+            deliveries: vec![],
+            unrouted: vec![],
+        }
+    }
+
+    /// Fails when a star's hub is not one of the machines
+    pub fn with_topology(
+        machines: Vec<Machine>,
+        topology: Topology,
+    ) -> Result<Network, UnknownMachine> {
+        let mut net = Network::new(machines);
+        let n = net.nodes.len();
+        match topology {
+            Topology::Ring | Topology::Chain => {
+                for i in 0..n.saturating_sub(1) {
+                    net.connect(i, i + 1)?;
+                }
+                if topology == Topology::Ring && n > 0 {
+                    net.connect(n - 1, 0)?;
+                }
+            }
+            Topology::Star { hub } => {
+                net.check(hub)?;
+                for i in (0..n).filter(|i| *i != hub) {
+                    net.connect(hub, i)?.connect(i, hub)?;
+                }
+            }
+            Topology::FullyConnected => {
+                for i in 0..n {
+                    for j in (0..n).filter(|j| *j != i) {
+                        net.connect(i, j)?;
+                    }
+                }
+            }
+            Topology::Bus { size } => net.packet_size = Some(size.max(1)),
+        }
+        Ok(net)
+    }
+
+    fn check(&self, id: usize) -> Result<(), UnknownMachine> {
+        if id < self.nodes.len() {
+            return Ok(());
+        }
+        Err(UnknownMachine {
+            id,
+            machines: self.nodes.len(),
+        })
+    }
+
+    /// Delivers every output of `from` to the input of `to`
+    pub fn connect(&mut self, from: usize, to: usize) -> Result<&mut Self, UnknownMachine> {
+        self.check(from)?;
+        self.check(to)?;
+        self.nodes[from].edges.push(to);
+        Ok(self)
+    }
+
+    pub fn send(&mut self, to: usize, value: LargeNumber) -> Result<&mut Self, UnknownMachine> {
+        self.check(to)?;
+        self.push(None, to, value);
+        Ok(self)
+    }
+
+    /// Queues `value` on the input of `to` and records the delivery
+    fn push(&mut self, from: Option<usize>, to: usize, value: LargeNumber) {
+        self.nodes[to].machine.io.input.push_back(value);
+        self.deliveries.push(Delivery {
+            seq: self.deliveries.len(),
+            round: self.rounds,
+            from,
+            to,
+            value,
+        });
+    }
+
+    pub fn machine(&self, id: usize) -> Result<&Machine, UnknownMachine> {
+        self.check(id)?;
+        Ok(&self.nodes[id].machine)
+    }
+
+    pub fn machine_mut(&mut self, id: usize) -> Result<&mut Machine, UnknownMachine> {
+        self.check(id)?;
+        Ok(&mut self.nodes[id].machine)
+    }
+
+    /// Runs every machine in turn on the current thread
+    pub fn run(&mut self) -> NetworkReport {
+        self.run_with(1)
+    }
+
+    /// Spreads the machines of every round over `workers` threads, which
+    /// are started once and kept for the whole run. Machines move to and
+    /// from the workers every round, so this pays off when rounds do
+    /// a lot of work
+    pub fn run_parallel(&mut self, workers: usize) -> NetworkReport {
+        self.run_with(workers.max(1))
+    }
+
+    fn run_with(&mut self, workers: usize) -> NetworkReport {
+        if workers == 1 {
+            return self.run_rounds(|nodes| run_all(nodes.iter_mut().filter(|n| n.runnable())));
+        }
+        std::thread::scope(|s| {
+            // dropped before the scope joins the workers, which stops them
+            let pool = Pool::start(s, workers);
+            self.run_rounds(|nodes| pool.round(nodes))
+        })
+    }
+
+    fn run_rounds<F: FnMut(&mut Vec<Node>) -> bool>(&mut self, mut round: F) -> NetworkReport {
+        let mut rounds = 0;
+        loop {
+            rounds += 1;
+            let progressed = round(&mut self.nodes);
+            self.deliver();
+            self.rounds += 1;
+            if !self.nodes.iter().any(Node::runnable) {
+                return self.report(rounds, false);
+            }
+            if !progressed {
+                return self.report(rounds, true);
+            }
+        }
+    }
+
+    fn deliver(&mut self) {
+        for from in 0..self.nodes.len() {
+            let node = &mut self.nodes[from];
+            let pending = node
+                .machine
+                .io
+                .output
+                .iter()
+                .skip(node.delivered)
+                .copied()
+                .collect::<Vec<_>>();
+            match self.packet_size {
+                None => {
+                    node.delivered += pending.len();
+                    for value in pending {
+                        for to in self.nodes[from].edges.clone() {
+                            self.push(Some(from), to, value);
+                        }
+                    }
+                }
+                Some(size) => {
+                    for packet in pending.chunks_exact(size) {
+                        self.nodes[from].delivered += size;
+                        match usize::try_from(packet[0])
+                            .ok()
+                            .filter(|to| *to < self.nodes.len())
+                        {
+                            Some(to) => {
+                                for value in &packet[1..] {
+                                    self.push(Some(from), to, *value);
+                                }
+                            }
+                            None => self.unrouted.push(packet.to_vec()),
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    fn report(&self, rounds: usize, deadlocked: bool) -> NetworkReport {
+        let machines = self
+            .nodes
+            .iter()
+            .map(|n| MachineReport {
+                status: match (&n.error, n.machine.state) {
+                    (Some(e), _) => MachineStatus::Failed(e.clone()),
+                    (None, ComputerState::Halted) => MachineStatus::Halted,
+                    (None, _) => MachineStatus::WaitingForInput,
+                },
+                pc: n.machine.pc,
+                steps: n.machine.steps(),
+                outputs: n.machine.io.output.iter().copied().collect(),
+            })
+            .collect();
+        NetworkReport {
+            rounds,
+            deadlocked,
+            machines,
+        }
+    }
+}
+
+#[cfg(test)]
+fn amplifiers(prog: &[LargeNumber], phases: &[LargeNumber]) -> Vec<Machine> {
+    phases
+        .iter()
+        .map(|p| IntCodeComputer::with_io(prog.to_vec(), QueueIo::new(vec![*p])))
+        .collect()
+}
+
+#[test]
+fn test_ring_feedback_loop() {
+    let run = |workers| {
+        let amps = amplifiers(&super::FEEDBACK_LOOP, &[9, 8, 7, 6, 5]);
+        let mut net = Network::with_topology(amps, Topology::Ring).unwrap();
+        net.send(0, 0).unwrap();
+        (net.run_parallel(workers), net.deliveries)
+    };
+    let (report, deliveries) = run(1);
+    // one amplifier runs on a new signal per round, five times round the
+    // ring, the last one halting in the round it sends its final signal
+    assert_eq!(report.rounds, 25);
+    assert!(!report.deadlocked);
+    assert!(report
+        .machines
+        .iter()
+        .all(|m| m.status == MachineStatus::Halted && m.outputs.len() == 5));
+    // the threads a round runs on do not change the outcome
+    for workers in 2..=3 {
+        assert_eq!(run(workers), (report.clone(), deliveries.clone()));
+    }
+}
+
+#[test]
+fn test_deadlock_detection() {
+    // every machine waits for input before producing anything
+    let prog = vec![3, 9, 4, 9, 99];
+    let mut net = Network::with_topology(amplifiers(&prog, &[]), Topology::Ring).unwrap();
+    assert_eq!(net.run().rounds, 1);
+
+    let machines = (0..3)
+        .map(|_| IntCodeComputer::with_io(prog.clone(), QueueIo::default()))
+        .collect();
+    let report = Network::with_topology(machines, Topology::FullyConnected)
+        .unwrap()
+        .run();
+    assert!(report.deadlocked);
+    assert_eq!(report.waiting(), vec![0, 1, 2]);
+    assert!(report.machines.iter().all(|m| m.pc == 0));
+}
+
+#[test]
+fn test_packet_bus() {
+    // sends its input as the payload of a packet to machine 1 and to 9
+    let sender = vec![3, 100, 104, 1, 4, 100, 104, 9, 4, 100, 99];
+    let receiver = vec![3, 100, 1002, 100, 2, 100, 4, 100, 99];
+    let mut net = Network::with_topology(
+        vec![
+            IntCodeComputer::with_io(sender, QueueIo::new(vec![21])),
+            IntCodeComputer::with_io(receiver, QueueIo::default()),
+        ],
+        Topology::Bus { size: 2 },
+    )
+    .unwrap();
+    let report = net.run();
+    assert!(!report.deadlocked);
+    assert_eq!(report.machines[1].outputs, vec![42]);
+    assert_eq!(net.unrouted, vec![vec![9, 21]]);
+    assert_eq!(
+        net.deliveries,
+        vec![Delivery {
+            seq: 0,
+            round: 0,
+            from: Some(0),
+            to: 1,
+            value: 21
+        }]
+    );
+}
+
+#[test]
+fn test_unknown_machine() {
+    let prog = vec![3, 9, 4, 9, 99];
+    let err = UnknownMachine { id: 3, machines: 3 };
+    let star = Network::with_topology(amplifiers(&prog, &[1, 2, 3]), Topology::Star { hub: 3 });
+    assert_eq!(star.err(), Some(err));
+    assert_eq!(err.to_string(), "no machine 3 in a network of 3");
+
+    let mut net =
+        Network::with_topology(amplifiers(&prog, &[1, 2, 3]), Topology::Star { hub: 1 }).unwrap();
+    assert_eq!(net.connect(0, 3).err(), Some(err));
+    assert_eq!(net.send(5, 1).err().map(|e| e.id), Some(5));
+    assert_eq!(net.machine(3).err(), Some(err));
+    assert!(net.machine_mut(2).is_ok());
+    assert_eq!(net.run().machines[1].outputs, vec![2]);
+}
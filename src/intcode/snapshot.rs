@@ -0,0 +1,273 @@
+// Snapshots of the full machine state.
+//
+// Snapshots serialise to a line based text format:
+//
+//     pc 25
+//     relbase 1000
+//     state WaitingForInput
+//     steps 9
+//     input 1,2
+//     output
+//     mem 0 1102,34463338,34463338,63
+//
+// with one `mem <start> <cells>` line per allocated memory segment.
+//
+use super::{
+    ComputerState, IntCodeComputer, IntcodeError, IntcodeIo, LargeNumber, Limit, MemoryBackend,
+    QueueIo,
+};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Snapshot {
+    pub pc: usize,
+    pub relbase: LargeNumber,
+    pub state: ComputerState,
+    pub steps: usize,
+    pub input: Vec<LargeNumber>,
+    pub output: Vec<LargeNumber>,
+    pub mem: Vec<(usize, Vec<LargeNumber>)>,
+}
+
+fn join(values: &[LargeNumber]) -> String {
+    values
+        .iter()
+        .map(|v| v.to_string())
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn invalid(line: usize, msg: &str) -> io::Error {
+    io::Error::new(
+        io::ErrorKind::InvalidData,
+        format!("snapshot line {}: {}", line, msg),
+    )
+}
+
+impl fmt::Display for Snapshot {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "pc {}", self.pc)?;
+        writeln!(f, "relbase {}", self.relbase)?;
+        writeln!(f, "state {:?}", self.state)?;
+        writeln!(f, "steps {}", self.steps)?;
+        writeln!(f, "input {}", join(&self.input))?;
+        writeln!(f, "output {}", join(&self.output))?;
+        for (start, cells) in &self.mem {
+            writeln!(f, "mem {} {}", start, join(cells))?;
+        }
+        Ok(())
+    }
+}
+
+impl Snapshot {
+    pub fn parse(s: &str) -> io::Result<Snapshot> {
+        let mut snap = Snapshot {
+            pc: 0,
+            relbase: 0,
+            state: ComputerState::Running,
+            steps: 0,
+            input: vec![],
+            output: vec![],
+            mem: vec![],
+        };
+        for (idx, line) in s.lines().enumerate() {
+            let line_no = idx + 1;
+            let (key, rest) = match line.trim().split_once(' ') {
+                Some((key, rest)) => (key, rest.trim()),
+                None => (line.trim(), ""),
+            };
+            let num = |s: &str| s.parse().map_err(|_| invalid(line_no, "invalid number"));
+            let addr = |s: &str| s.parse().map_err(|_| invalid(line_no, "invalid address"));
+            let list = |s: &str| -> io::Result<Vec<LargeNumber>> {
+                if s.is_empty() {
+                    return Ok(vec![]);
+                }
+                s.split(',')
+                    .map(|x| {
+                        x.trim()
+                            .parse()
+                            .map_err(|_| invalid(line_no, "invalid cell"))
+                    })
+                    .collect()
+            };
+            match key {
+                "" => {}
+                "pc" => snap.pc = addr(rest)?,
+                "relbase" => snap.relbase = num(rest)?,
+                "steps" => snap.steps = addr(rest)?,
+                "state" => {
+                    snap.state = match rest {
+                        "Running" => ComputerState::Running,
+                        "WaitingForInput" => ComputerState::WaitingForInput,
+                        "Halted" => ComputerState::Halted,
+                        _ => return Err(invalid(line_no, "unknown state")),
+                    }
+                }
+                "input" => snap.input = list(rest)?,
+                "output" => snap.output = list(rest)?,
+                "mem" => {
+                    let (start, cells) = rest.split_once(' ').unwrap_or((rest, ""));
+                    snap.mem.push((addr(start)?, list(cells.trim())?));
+                }
+                _ => return Err(invalid(line_no, "unknown field")),
+            }
+        }
+        Ok(snap)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        fs::write(path, self.to_string())
+    }
+
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Snapshot> {
+        Snapshot::parse(&fs::read_to_string(path)?)
+    }
+}
+
+impl<IO: IntcodeIo + Clone, M: MemoryBackend + Clone> IntCodeComputer<IO, M> {
+    /// Copies the machine, without its tracer, to branch off the current state
+    pub fn fork(&self) -> IntCodeComputer<IO, M> {
+        IntCodeComputer {
+            pc: self.pc,
+            mem: self.mem.clone(),
+            state: self.state,
+            io: self.io.clone(),
+            pc_mod: false,
+            relbase: self.relbase,
+            ins_pc: self.ins_pc,
+            steps: self.steps,
+            limits: self.limits,
+            deadline: self.deadline,
+            limited: self.limited,
+            tracer: None,
+            decoded: self.decoded.clone(),
+            decode_cache: self.decode_cache,
+        }
+    }
+}
+
+impl<M: MemoryBackend + Default> IntCodeComputer<QueueIo, M> {
+    pub fn from_snapshot(snap: &Snapshot) -> Result<IntCodeComputer<QueueIo, M>, IntcodeError> {
+        let mut c = IntCodeComputer::with_memory(M::default(), QueueIo::default());
+        c.restore(snap)?;
+        Ok(c)
+    }
+
+    pub fn snapshot(&self) -> Snapshot {
+        Snapshot {
+            pc: self.pc,
+            relbase: self.relbase,
+            // limits are not saved, so a stopped machine restores as running
+            state: match self.state {
+                ComputerState::Stopped(_) => ComputerState::Running,
+                state => state,
+            },
+            steps: self.steps,
+            input: self.io.input.iter().copied().collect(),
+            output: self.io.output.iter().copied().collect(),
+            mem: self.mem.segments(),
+        }
+    }
+
+    /// Replaces the machine state, including pending I/O, with the snapshot's.
+    /// The memory limit applies to the restored cells, and on failure the
+    /// machine is left unchanged
+    pub fn restore(&mut self, snap: &Snapshot) -> Result<(), IntcodeError> {
+        let pc = snap.pc;
+        let mut mem = M::default();
+        for (start, cells) in &snap.mem {
+            let end = start
+                .checked_add(cells.len())
+                .ok_or(IntcodeError::OutOfMemory {
+                    pc,
+                    addr: usize::MAX,
+                })?;
+            if let Some(limit) = self.limits.memory.filter(|l| end > *l) {
+                let limit = Limit::Memory(limit);
+                return Err(IntcodeError::LimitExceeded { pc, limit });
+            }
+            for (i, value) in cells.iter().enumerate() {
+                mem.store(start + i, *value)
+                    .map_err(|e| IntcodeError::OutOfMemory { pc, addr: e.addr })?;
+            }
+        }
+        self.mem = mem;
+        self.pc = snap.pc;
+        self.relbase = snap.relbase;
+        self.state = snap.state;
+        self.steps = snap.steps;
+        self.io.input = snap.input.iter().copied().collect();
+        self.io.output = snap.output.iter().copied().collect();
+        Ok(())
+    }
+}
+
+#[test]
+fn test_snapshot_round_trip() {
+    // echoes inputs doubled, forever
+    let prog = vec![3, 100, 1002, 100, 2, 100, 4, 100, 1105, 1, 0];
+    let mut c = IntCodeComputer::with_io(prog, QueueIo::new(vec![1, 2]));
+    c.run_until(|_| false).unwrap();
+    c.io.input.push_back(3);
+    c.step().unwrap();
+
+    let snap = c.snapshot();
+    let path = std::env::temp_dir().join(format!("intcode-snapshot-{}.txt", std::process::id()));
+    snap.save(&path).unwrap();
+    let loaded = Snapshot::load(&path).unwrap();
+    fs::remove_file(&path).unwrap();
+    assert_eq!(loaded, snap);
+
+    let mut restored: IntCodeComputer<QueueIo> = IntCodeComputer::from_snapshot(&loaded).unwrap();
+    assert_eq!(restored.mem, c.mem);
+    restored.io.input.push_back(4);
+    restored.run_until(|_| false).unwrap();
+    assert_eq!(restored.io.output, vec![2, 4, 6, 8]);
+
+    // hostile segments fail without touching the machine
+    let mut huge = snap.clone();
+    huge.mem.push((usize::MAX / 2, vec![1]));
+    assert!(matches!(
+        IntCodeComputer::<QueueIo>::from_snapshot(&huge),
+        Err(IntcodeError::OutOfMemory { .. })
+    ));
+    huge.mem = vec![(usize::MAX, vec![1, 2])];
+    assert!(matches!(
+        IntCodeComputer::<QueueIo>::from_snapshot(&huge),
+        Err(IntcodeError::OutOfMemory { .. })
+    ));
+    let mut far = snap.clone();
+    far.mem.push((1000, vec![1]));
+    restored.with_memory_limit(1000);
+    let before = restored.snapshot();
+    assert_eq!(
+        restored.restore(&far),
+        Err(IntcodeError::LimitExceeded {
+            pc: snap.pc,
+            limit: Limit::Memory(1000)
+        })
+    );
+    assert_eq!(restored.snapshot(), before);
+}
+
+#[test]
+fn test_fork() {
+    let prog = vec![3, 100, 1002, 100, 2, 100, 4, 100, 1105, 1, 0];
+    let base = IntCodeComputer::with_io(prog, QueueIo::default());
+    let mut forks = (1..=3)
+        .map(|i| {
+            let mut c = base.fork();
+            c.io.input.push_back(i);
+            c
+        })
+        .collect::<Vec<_>>();
+    for c in forks.iter_mut() {
+        c.run_until(|_| false).unwrap();
+    }
+    let outputs = forks.iter().map(|c| c.io.output[0]).collect::<Vec<_>>();
+    assert_eq!(outputs, vec![2, 4, 6]);
+    assert_eq!(base.steps(), 0);
+}
@@ -0,0 +1,328 @@
+// A small solver for integer linear constraints.
+//
+// Narrows the range of every variable from the constraints until nothing
+// changes, then splits the smallest range in two and recurses, trying the
+// half nearer zero first. Variables range over the `i64` values a cell
+// can hold. Bounds are worked out in `i128`, and a constraint whose
+// bounds do not fit even that does not narrow anything.
+//
+use super::symbolic::Var;
+use super::LargeNumber;
+use std::collections::BTreeMap;
+use std::fmt;
+
+pub type Model = BTreeMap<Var, LargeNumber>;
+
+/// Relation of a linear expression to zero
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Rel {
+    Eq,
+    Ne,
+    Lt,
+    Ge,
+}
+
+impl Rel {
+    pub fn holds(self, value: LargeNumber) -> bool {
+        match self {
+            Rel::Eq => value == 0,
+            Rel::Ne => value != 0,
+            Rel::Lt => value < 0,
+            Rel::Ge => value >= 0,
+        }
+    }
+
+    pub fn negate(self) -> Rel {
+        match self {
+            Rel::Eq => Rel::Ne,
+            Rel::Ne => Rel::Eq,
+            Rel::Lt => Rel::Ge,
+            Rel::Ge => Rel::Lt,
+        }
+    }
+}
+
+/// `constant + sum(coefficient * var)`
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Linear {
+    pub terms: BTreeMap<Var, LargeNumber>,
+    pub constant: LargeNumber,
+}
+
+impl Linear {
+    /// `None` when the sum leaves `i128`
+    fn eval(&self, model: &Model) -> Option<i128> {
+        self.terms
+            .iter()
+            .try_fold(self.constant as i128, |sum, (v, a)| {
+                sum.checked_add(*a as i128 * model[v] as i128)
+            })
+    }
+}
+
+impl fmt::Display for Linear {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut first = true;
+        for (v, a) in &self.terms {
+            match (first, *a < 0) {
+                (true, true) => write!(f, "-")?,
+                (true, false) => {}
+                (false, true) => write!(f, " - ")?,
+                (false, false) => write!(f, " + ")?,
+            }
+            match a.abs() {
+                1 => write!(f, "{}", v)?,
+                a => write!(f, "{}*{}", a, v)?,
+            }
+            first = false;
+        }
+        match (first, self.constant) {
+            (true, c) => write!(f, "{}", c),
+            (false, 0) => Ok(()),
+            (false, c) if c < 0 => write!(f, " - {}", -c),
+            (false, c) => write!(f, " + {}", c),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Check {
+    Sat(Model),
+    Unsat,
+    /// Gave up, see `NODE_LIMIT`, or the arithmetic left `i128`
+    Unknown,
+}
+
+/// Ranges split before giving up
+const NODE_LIMIT: usize = 100_000;
+
+type Bounds = BTreeMap<Var, (i128, i128)>;
+
+/// Finds values for the variables meeting every constraint
+pub fn solve(constraints: &[(Linear, Rel)]) -> Check {
+    let bounds = constraints
+        .iter()
+        .flat_map(|(l, _)| l.terms.keys())
+        .map(|v| (*v, (LargeNumber::MIN as i128, LargeNumber::MAX as i128)))
+        .collect();
+    let mut nodes = 0;
+    search(constraints, bounds, &mut nodes)
+}
+
+fn search(constraints: &[(Linear, Rel)], mut bounds: Bounds, nodes: &mut usize) -> Check {
+    *nodes += 1;
+    if *nodes > NODE_LIMIT {
+        return Check::Unknown;
+    }
+    if !propagate(constraints, &mut bounds) {
+        return Check::Unsat;
+    }
+    let open = bounds
+        .iter()
+        .filter(|(_, (lo, hi))| lo < hi)
+        .min_by_key(|(_, (lo, hi))| hi - lo);
+    let (var, (lo, hi)) = match open {
+        Some((var, range)) => (*var, *range),
+        None => {
+            let model = bounds.iter().map(|(v, (x, _))| (*v, *x as i64)).collect();
+            let mut sat = true;
+            for (l, rel) in constraints {
+                match l.eval(&model) {
+                    Some(value) => sat &= rel.holds(value.signum() as LargeNumber),
+                    None => return Check::Unknown,
+                }
+            }
+            return if sat { Check::Sat(model) } else { Check::Unsat };
+        }
+    };
+    let (first, second) = if lo < 0 && hi >= 0 {
+        ((0, hi), (lo, -1))
+    } else if lo >= 0 {
+        let mid = lo + (hi - lo) / 2;
+        ((lo, mid), (mid + 1, hi))
+    } else {
+        let mid = hi - (hi - lo) / 2;
+        ((mid, hi), (lo, mid - 1))
+    };
+    let mut unknown = false;
+    for half in [first, second] {
+        let mut narrowed = bounds.clone();
+        narrowed.insert(var, half);
+        match search(constraints, narrowed, nodes) {
+            Check::Sat(model) => return Check::Sat(model),
+            Check::Unknown => unknown = true,
+            Check::Unsat => {}
+        }
+    }
+    if unknown {
+        Check::Unknown
+    } else {
+        Check::Unsat
+    }
+}
+
+/// Narrows the bounds until they settle, false when one becomes empty
+fn propagate(constraints: &[(Linear, Rel)], bounds: &mut Bounds) -> bool {
+    // ranges can shrink by one per round, so stop early and split instead
+    for _ in 0..64 {
+        let mut changed = false;
+        for (l, rel) in constraints {
+            if !narrow(l, *rel, bounds, &mut changed) {
+                return false;
+            }
+        }
+        if !changed {
+            break;
+        }
+    }
+    true
+}
+
+fn div_floor(a: i128, b: i128) -> Option<i128> {
+    let q = a.checked_div(b)?;
+    Some(if a % b != 0 && (a < 0) != (b < 0) {
+        q - 1
+    } else {
+        q
+    })
+}
+
+fn div_ceil(a: i128, b: i128) -> Option<i128> {
+    let q = a.checked_div(b)?;
+    Some(if a % b != 0 && (a < 0) == (b < 0) {
+        q + 1
+    } else {
+        q
+    })
+}
+
+/// Range of `v` for which `a * v + rest` can meet `rel`, given the range
+/// of `rest`, `None` when the arithmetic leaves `i128`
+fn tighten(
+    a: i128,
+    rel: Rel,
+    (rest_min, rest_max): (i128, i128),
+    (lo, hi): (i128, i128),
+) -> Option<(i128, i128)> {
+    Some(match rel {
+        // v differs from the one value that zeroes the sum
+        Rel::Ne => {
+            let zero = rest_min.checked_neg()?;
+            match (rest_min == rest_max, zero.checked_div(a)?) {
+                (true, skip) if zero % a == 0 && skip == lo => (lo + 1, hi),
+                (true, skip) if zero % a == 0 && skip == hi => (lo, hi - 1),
+                _ => (lo, hi),
+            }
+        }
+        _ => {
+            // range `a * v` has to stay within
+            let (min, max) = match rel {
+                Rel::Lt => (None, Some((-1i128).checked_sub(rest_min)?)),
+                Rel::Ge => (Some(rest_max.checked_neg()?), None),
+                _ => (Some(rest_max.checked_neg()?), Some(rest_min.checked_neg()?)),
+            };
+            let (min, max) = if a > 0 { (min, max) } else { (max, min) };
+            let lo = match min {
+                Some(m) => lo.max(div_ceil(m, a)?),
+                None => lo,
+            };
+            let hi = match max {
+                Some(m) => hi.min(div_floor(m, a)?),
+                None => hi,
+            };
+            (lo, hi)
+        }
+    })
+}
+
+fn narrow(l: &Linear, rel: Rel, bounds: &mut Bounds, changed: &mut bool) -> bool {
+    let terms = l
+        .terms
+        .iter()
+        .map(|(v, a)| {
+            let (lo, hi) = bounds[v];
+            let a = *a as i128;
+            let (tlo, thi) = if a > 0 {
+                (a * lo, a * hi)
+            } else {
+                (a * hi, a * lo)
+            };
+            (*v, a, tlo, thi)
+        })
+        .collect::<Vec<_>>();
+    let sum = |part: fn(&(Var, i128, i128, i128)) -> i128| {
+        terms
+            .iter()
+            .try_fold(l.constant as i128, |sum, t| sum.checked_add(part(t)))
+    };
+    let (smin, smax) = match (sum(|t| t.2), sum(|t| t.3)) {
+        (Some(smin), Some(smax)) => (smin, smax),
+        _ => return true,
+    };
+
+    for (v, a, tlo, thi) in &terms {
+        let bound = bounds[v];
+        let rest = smin.checked_sub(*tlo).zip(smax.checked_sub(*thi));
+        let (lo, hi) = rest
+            .and_then(|rest| tighten(*a, rel, rest, bound))
+            .unwrap_or(bound);
+        if lo > hi {
+            return false;
+        }
+        if (lo, hi) != bounds[v] {
+            bounds.insert(*v, (lo, hi));
+            *changed = true;
+        }
+    }
+    match rel {
+        Rel::Ne if smin == smax => smin != 0,
+        _ => true,
+    }
+}
+
+#[test]
+fn test_solve() {
+    let x = Var::Input(0);
+    let y = Var::Input(1);
+    let lin = |terms: &[(Var, LargeNumber)], constant| Linear {
+        terms: terms.iter().copied().collect(),
+        constant,
+    };
+    let model = |pairs: &[(Var, LargeNumber)]| Check::Sat(pairs.iter().copied().collect());
+
+    // x + y = 10, x - y >= 4, x != 7
+    let cs = vec![
+        (lin(&[(x, 1), (y, 1)], -10), Rel::Eq),
+        (lin(&[(x, 1), (y, -1)], -4), Rel::Ge),
+        (lin(&[(x, 1)], -7), Rel::Ne),
+    ];
+    assert_eq!(solve(&cs), model(&[(x, 8), (y, 2)]));
+
+    // 2x = 7 has no integer solution
+    assert_eq!(solve(&[(lin(&[(x, 2)], -7), Rel::Eq)]), Check::Unsat);
+    // x < 3 and x >= 3
+    assert_eq!(
+        solve(&[(lin(&[(x, 1)], -3), Rel::Lt), (lin(&[(x, 1)], -3), Rel::Ge)]),
+        Check::Unsat
+    );
+    // closest to zero first, negative values only when forced
+    assert_eq!(solve(&[(lin(&[(x, 1)], 0), Rel::Ne)]), model(&[(x, 1)]));
+    assert_eq!(solve(&[(lin(&[(x, 1)], 5), Rel::Lt)]), model(&[(x, -6)]));
+    assert_eq!(solve(&[]), Check::Sat(Model::new()));
+
+    // values past i32 are cells like any other
+    let big = 1 << 40;
+    assert_eq!(
+        solve(&[(lin(&[(x, 1)], -big), Rel::Eq)]),
+        model(&[(x, big)])
+    );
+    assert_eq!(
+        solve(&[(lin(&[(x, -3)], LargeNumber::MIN), Rel::Ge)]),
+        model(&[(x, -3074457345618258603)])
+    );
+    // 2x = max has no solution even in i64
+    assert_eq!(
+        solve(&[(lin(&[(x, 2)], -LargeNumber::MAX), Rel::Eq)]),
+        Check::Unsat
+    );
+}
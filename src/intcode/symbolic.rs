@@ -0,0 +1,765 @@
+// Symbolic execution of Intcode programs.
+//
+// Inputs read by `STO` and chosen memory cells become variables, and
+// arithmetic on them builds expressions. A `JZ`, `JNZ`, `LT` or `EQ` whose
+// outcome depends on variables forks the run in two, each path recording
+// the constraint it took. Paths the solver proves impossible are dropped.
+//
+// Opcodes and addresses must stay concrete: a path that would jump to,
+// read from or write to a symbolic address ends with `End::Symbolic`. With
+// opaque reads on, reading through a symbolic address gives a fresh
+// `Var::Opaque` instead, which nothing constrains. Arithmetic on two
+// constants that overflows ends the path with `IntcodeError::Overflow`,
+// as it would on a real machine. Whether arithmetic on variables
+// overflows depends on their values, which paths do not track: constants
+// that would overflow when folded into a symbolic sum or product are left
+// unfolded instead.
+//
+use super::solver::{self, Check, Linear, Model, Rel};
+use super::{AddressingMode, Instruction, IntcodeError, LargeNumber, Opcode, Program};
+use std::collections::BTreeSet;
+use std::fmt;
+use std::rc::Rc;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Var {
+    /// The nth value read by `STO`, counted from 0
+    Input(usize),
+    /// The initial value of a memory cell
+    Cell(usize),
+    /// The nth value read through a symbolic address
+    Opaque(usize),
+}
+
+impl fmt::Display for Var {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Var::Input(n) => write!(f, "in{}", n),
+            Var::Cell(addr) => write!(f, "mem[{}]", addr),
+            Var::Opaque(n) => write!(f, "opaque{}", n),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum Expr {
+    Const(LargeNumber),
+    Var(Var),
+    Add(Rc<Expr>, Rc<Expr>),
+    Mul(Rc<Expr>, Rc<Expr>),
+}
+
+impl Expr {
+    /// Folds constants, keeping them last in sums, `None` when both are
+    /// constant and their sum overflows
+    pub fn plus(a: Expr, b: Expr) -> Option<Expr> {
+        Some(match (a, b) {
+            (Expr::Const(x), Expr::Const(y)) => Expr::Const(x.checked_add(y)?),
+            (Expr::Const(0), e) | (e, Expr::Const(0)) => e,
+            (Expr::Const(c), e) => return Expr::plus(e, Expr::Const(c)),
+            (Expr::Add(x, c), Expr::Const(d)) => {
+                match c.constant().and_then(|c| c.checked_add(d)) {
+                    Some(sum) => return Expr::plus((*x).clone(), Expr::Const(sum)),
+                    None => Expr::Add(Rc::new(Expr::Add(x, c)), Rc::new(Expr::Const(d))),
+                }
+            }
+            (a, b) => Expr::Add(Rc::new(a), Rc::new(b)),
+        })
+    }
+
+    /// Folds constants, keeping them first in products, `None` when both
+    /// are constant and their product overflows
+    pub fn times(a: Expr, b: Expr) -> Option<Expr> {
+        Some(match (a, b) {
+            (Expr::Const(x), Expr::Const(y)) => Expr::Const(x.checked_mul(y)?),
+            (Expr::Const(0), _) | (_, Expr::Const(0)) => Expr::Const(0),
+            (Expr::Const(1), e) | (e, Expr::Const(1)) => e,
+            (e, Expr::Const(c)) => return Expr::times(Expr::Const(c), e),
+            (Expr::Const(c), Expr::Mul(d, x)) => {
+                match d.constant().and_then(|d| c.checked_mul(d)) {
+                    Some(product) => return Expr::times(Expr::Const(product), (*x).clone()),
+                    None => Expr::Mul(Rc::new(Expr::Const(c)), Rc::new(Expr::Mul(d, x))),
+                }
+            }
+            (a, b) => Expr::Mul(Rc::new(a), Rc::new(b)),
+        })
+    }
+
+    /// `a - b`, left unfolded where folding would overflow
+    pub fn minus(a: Expr, b: Expr) -> Expr {
+        let negated = match Expr::times(Expr::Const(-1), b.clone()) {
+            Some(negated) => negated,
+            None => Expr::Mul(Rc::new(Expr::Const(-1)), Rc::new(b)),
+        };
+        match Expr::plus(a.clone(), negated.clone()) {
+            Some(difference) => difference,
+            None => Expr::Add(Rc::new(a), Rc::new(negated)),
+        }
+    }
+
+    pub fn constant(&self) -> Option<LargeNumber> {
+        match self {
+            Expr::Const(c) => Some(*c),
+            _ => None,
+        }
+    }
+
+    /// Variables the expression depends on
+    pub fn vars(&self) -> BTreeSet<Var> {
+        match self {
+            Expr::Const(_) => BTreeSet::new(),
+            Expr::Var(v) => vec![*v].into_iter().collect(),
+            Expr::Add(a, b) | Expr::Mul(a, b) => a.vars().union(&b.vars()).copied().collect(),
+        }
+    }
+
+    /// Value under `model`, taking variables it leaves out as 0
+    pub fn eval(&self, model: &Model) -> Option<LargeNumber> {
+        match self {
+            Expr::Const(c) => Some(*c),
+            Expr::Var(v) => Some(model.get(v).copied().unwrap_or(0)),
+            Expr::Add(a, b) => a.eval(model)?.checked_add(b.eval(model)?),
+            Expr::Mul(a, b) => a.eval(model)?.checked_mul(b.eval(model)?),
+        }
+    }
+
+    /// The expression as `constant + sum(coefficient * var)`, `None` when
+    /// variables are multiplied together
+    pub fn linear(&self) -> Option<Linear> {
+        match self {
+            Expr::Const(c) => Some(Linear {
+                constant: *c,
+                ..Linear::default()
+            }),
+            Expr::Var(v) => Some(Linear {
+                terms: vec![(*v, 1)].into_iter().collect(),
+                constant: 0,
+            }),
+            Expr::Add(a, b) => {
+                let (mut sum, b) = (a.linear()?, b.linear()?);
+                for (v, c) in b.terms {
+                    let total = sum.terms.get(&v).unwrap_or(&0).checked_add(c)?;
+                    if total == 0 {
+                        sum.terms.remove(&v);
+                    } else {
+                        sum.terms.insert(v, total);
+                    }
+                }
+                sum.constant = sum.constant.checked_add(b.constant)?;
+                Some(sum)
+            }
+            Expr::Mul(a, b) => {
+                let (a, b) = (a.linear()?, b.linear()?);
+                let (k, mut l) = match (a.terms.is_empty(), b.terms.is_empty()) {
+                    (true, _) => (a.constant, b),
+                    (_, true) => (b.constant, a),
+                    _ => return None,
+                };
+                for c in l.terms.values_mut() {
+                    *c = c.checked_mul(k)?;
+                }
+                l.constant = l.constant.checked_mul(k)?;
+                Some(l)
+            }
+        }
+    }
+}
+
+impl fmt::Display for Expr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Expr::Const(c) => write!(f, "{}", c),
+            Expr::Var(v) => write!(f, "{}", v),
+            Expr::Add(a, b) => match &**b {
+                Expr::Const(c) if *c < 0 => write!(f, "{} - {}", a, -c),
+                Expr::Mul(k, e) if **k == Expr::Const(-1) => write!(f, "{} - {}", a, e),
+                _ => write!(f, "{} + {}", a, b),
+            },
+            Expr::Mul(a, b) => {
+                let factor = |e: &Expr| match e {
+                    Expr::Add(..) => format!("({})", e),
+                    _ => e.to_string(),
+                };
+                write!(f, "{}*{}", factor(a), factor(b))
+            }
+        }
+    }
+}
+
+/// `expr <rel> 0`
+#[derive(Clone, Debug, PartialEq)]
+pub struct Constraint {
+    pub expr: Expr,
+    pub rel: Rel,
+}
+
+impl Constraint {
+    pub fn eq(a: Expr, b: Expr) -> Constraint {
+        Constraint {
+            expr: Expr::minus(a, b),
+            rel: Rel::Eq,
+        }
+    }
+
+    pub fn lt(a: Expr, b: Expr) -> Constraint {
+        Constraint {
+            expr: Expr::minus(a, b),
+            rel: Rel::Lt,
+        }
+    }
+
+    pub fn negate(&self) -> Constraint {
+        Constraint {
+            expr: self.expr.clone(),
+            rel: self.rel.negate(),
+        }
+    }
+}
+
+impl fmt::Display for Constraint {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let rel = match self.rel {
+            Rel::Eq => "==",
+            Rel::Ne => "!=",
+            Rel::Lt => "<",
+            Rel::Ge => ">=",
+        };
+        match self.expr.linear() {
+            Some(l) => write!(
+                f,
+                "{} {} {}",
+                Linear {
+                    constant: 0,
+                    ..l.clone()
+                },
+                rel,
+                -l.constant
+            ),
+            None => write!(f, "{} {} 0", self.expr, rel),
+        }
+    }
+}
+
+/// Solves the constraints, `Unknown` when one is not linear
+pub fn check(constraints: &[Constraint]) -> Check {
+    let linear = constraints
+        .iter()
+        .map(|c| c.expr.linear().map(|l| (l, c.rel)))
+        .collect::<Option<Vec<_>>>();
+    match linear {
+        Some(linear) => solver::solve(&linear),
+        None => Check::Unknown,
+    }
+}
+
+/// Why a path stopped
+#[derive(Clone, Debug, PartialEq)]
+pub enum End {
+    Halted,
+    Error(IntcodeError),
+    /// An opcode or address depends on variables
+    Symbolic {
+        pc: usize,
+    },
+    StepLimit {
+        pc: usize,
+    },
+    /// Left unexplored once the path limit was reached
+    Abandoned {
+        pc: usize,
+    },
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Path {
+    pub constraints: Vec<Constraint>,
+    pub outputs: Vec<Expr>,
+    /// Number of values read by `STO`
+    pub inputs: usize,
+    pub steps: usize,
+    pub end: End,
+    /// Memory when the path ended
+    pub mem: Vec<Expr>,
+}
+
+impl Path {
+    /// Values of the variables under which the program takes this path
+    /// and `extra` holds too
+    pub fn solve(&self, extra: &[Constraint]) -> Check {
+        check(&[&self.constraints[..], extra].concat())
+    }
+}
+
+/// One path being explored
+#[derive(Clone)]
+struct State {
+    pc: usize,
+    relbase: LargeNumber,
+    mem: Vec<Expr>,
+    inputs: usize,
+    outputs: Vec<Expr>,
+    constraints: Vec<Constraint>,
+    steps: usize,
+    /// Set once the path cannot go on
+    end: Option<End>,
+    opaque_reads: bool,
+    /// Values read through symbolic addresses so far
+    opaque: usize,
+}
+
+fn overflow(pc: usize) -> End {
+    End::Error(IntcodeError::Overflow { pc })
+}
+
+impl State {
+    fn load(&self, addr: usize) -> Expr {
+        self.mem.get(addr).cloned().unwrap_or(Expr::Const(0))
+    }
+
+    /// Fails when memory cannot grow to `addr`, blaming the instruction at `pc`
+    fn store(&mut self, pc: usize, addr: usize, value: Expr) -> Result<(), End> {
+        if self.mem.len() <= addr {
+            let out_of_memory = End::Error(IntcodeError::OutOfMemory { pc, addr });
+            let len = addr.checked_add(1).ok_or_else(|| out_of_memory.clone())?;
+            self.mem
+                .try_reserve(len - self.mem.len())
+                .map_err(|_| out_of_memory)?;
+            self.mem.resize(len, Expr::Const(0));
+        }
+        self.mem[addr] = value;
+        Ok(())
+    }
+
+    fn address(&self, value: &Expr, pc: usize) -> Result<usize, End> {
+        match value.constant() {
+            None => Err(End::Symbolic { pc }),
+            Some(addr) if addr < 0 => Err(End::Error(IntcodeError::NegativeAddress { pc, addr })),
+            Some(addr) => Ok(addr as usize),
+        }
+    }
+
+    /// Address the `n`th parameter of the instruction at `pc` points to
+    fn target(&self, pc: usize, n: usize, mode: AddressingMode) -> Result<usize, End> {
+        let param = self.load(pc + n);
+        match mode {
+            AddressingMode::Position => self.address(&param, pc),
+            AddressingMode::Relative => {
+                let addr =
+                    Expr::plus(param, Expr::Const(self.relbase)).ok_or_else(|| overflow(pc))?;
+                self.address(&addr, pc)
+            }
+            AddressingMode::Immediate => Err(End::Error(IntcodeError::ImmediateWrite { pc })),
+        }
+    }
+
+    fn read(&mut self, pc: usize, n: usize, mode: AddressingMode) -> Result<Expr, End> {
+        match mode {
+            AddressingMode::Immediate => Ok(self.load(pc + n)),
+            _ => match self.target(pc, n, mode) {
+                Ok(addr) => Ok(self.load(addr)),
+                Err(End::Symbolic { .. }) if self.opaque_reads => {
+                    self.opaque += 1;
+                    Ok(Expr::Var(Var::Opaque(self.opaque - 1)))
+                }
+                Err(end) => Err(end),
+            },
+        }
+    }
+
+    /// Whether `cond` holds on this path, along with a fork for when it
+    /// does not if both are feasible
+    fn branch(&mut self, cond: Constraint) -> (bool, Option<State>) {
+        let mut holds = self.constraints.clone();
+        let mut fails = self.constraints.clone();
+        fails.push(cond.negate());
+        holds.push(cond);
+        match (check(&holds) != Check::Unsat, check(&fails) != Check::Unsat) {
+            (true, true) => {
+                let mut other = self.clone();
+                other.constraints = fails;
+                self.constraints = holds;
+                (true, Some(other))
+            }
+            (holds, _) => (holds, None),
+        }
+    }
+
+    /// Executes one instruction, returning the fork it made if any
+    fn step(&mut self, inputs: &[LargeNumber]) -> Vec<State> {
+        let mut forks = match self.load(self.pc) {
+            Expr::Const(_) => vec![],
+            _ => self.concretize(),
+        };
+        if self.end.is_none() {
+            match self.execute(inputs) {
+                Ok(fork) => forks.extend(fork),
+                Err(end) => self.end = Some(end),
+            }
+        }
+        forks
+    }
+
+    /// Forks on every instruction the symbolic cell at the pc may hold,
+    /// this state taking the first
+    fn concretize(&mut self) -> Vec<State> {
+        let (pc, code) = (self.pc, self.load(self.pc));
+        let codes = instruction_codes()
+            .map(|value| (value, Constraint::eq(code.clone(), Expr::Const(value))))
+            .collect::<Vec<_>>();
+        let mut states = vec![];
+        let mut invalid = self.constraints.clone();
+        for (value, eq) in codes {
+            let mut constraints = self.constraints.clone();
+            constraints.push(eq.clone());
+            if check(&constraints) != Check::Unsat {
+                let mut state = self.clone();
+                state.constraints = constraints;
+                // the cell is already allocated, it holds the code
+                state.mem[pc] = Expr::Const(value);
+                states.push(state);
+            }
+            invalid.push(eq.negate());
+        }
+        // or no instruction at all
+        let end = match check(&invalid) {
+            Check::Unsat => None,
+            Check::Sat(model) => Some(End::Error(IntcodeError::UnknownOpcode {
+                pc,
+                opcode: code.eval(&model).unwrap_or(0),
+            })),
+            Check::Unknown => Some(End::Symbolic { pc }),
+        };
+        if let Some(end) = end {
+            let mut state = self.clone();
+            state.constraints = invalid;
+            state.end = Some(end);
+            states.push(state);
+        }
+        if states.is_empty() {
+            self.end = Some(End::Symbolic { pc });
+            return states;
+        }
+        *self = states.remove(0);
+        states
+    }
+
+    fn execute(&mut self, inputs: &[LargeNumber]) -> Result<Option<State>, End> {
+        let pc = self.pc;
+        let code = self.load(pc).constant().ok_or(End::Symbolic { pc })?;
+        let ins = Instruction::decode(code, pc).map_err(End::Error)?;
+        let next = pc + 1 + ins.opcode.arity();
+        self.pc = next;
+        let mut fork = None;
+        match ins.opcode {
+            Opcode::HLT => return Err(End::Halted),
+            Opcode::ADD | Opcode::MUL => {
+                let (a, b) = (self.read(pc, 1, ins.pm1)?, self.read(pc, 2, ins.pm2)?);
+                let value = match ins.opcode {
+                    Opcode::ADD => Expr::plus(a, b),
+                    _ => Expr::times(a, b),
+                };
+                let value = value.ok_or_else(|| overflow(pc))?;
+                self.store(pc, self.target(pc, 3, ins.pm3)?, value)?;
+            }
+            Opcode::STO => {
+                let value = match inputs.get(self.inputs) {
+                    Some(v) => Expr::Const(*v),
+                    None => Expr::Var(Var::Input(self.inputs)),
+                };
+                self.store(pc, self.target(pc, 1, ins.pm1)?, value)?;
+                self.inputs += 1;
+            }
+            Opcode::LOAD => {
+                let value = self.read(pc, 1, ins.pm1)?;
+                self.outputs.push(value);
+            }
+            Opcode::JNZ | Opcode::JZ => {
+                let value = self.read(pc, 1, ins.pm1)?;
+                let target = self.read(pc, 2, ins.pm2)?;
+                let zero = match value.constant() {
+                    Some(v) => v == 0,
+                    None => {
+                        let (zero, other) = self.branch(Constraint::eq(value, Expr::Const(0)));
+                        fork = other;
+                        zero
+                    }
+                };
+                let jump_if_zero = ins.opcode == Opcode::JZ;
+                let target = self.address(&target, pc);
+                let jump = |s: &mut State| match &target {
+                    Ok(addr) => s.pc = *addr,
+                    Err(end) => s.end = Some(end.clone()),
+                };
+                // the fork goes the other way
+                if zero == jump_if_zero {
+                    jump(self);
+                } else if let Some(other) = fork.as_mut() {
+                    jump(other);
+                }
+            }
+            Opcode::LT | Opcode::EQ => {
+                let (a, b) = (self.read(pc, 1, ins.pm1)?, self.read(pc, 2, ins.pm2)?);
+                let dest = self.target(pc, 3, ins.pm3)?;
+                let holds = match (a.constant(), b.constant(), ins.opcode) {
+                    (Some(a), Some(b), Opcode::LT) => a < b,
+                    (Some(a), Some(b), _) => a == b,
+                    (_, _, opcode) => {
+                        let cond = match opcode {
+                            Opcode::LT => Constraint::lt(a, b),
+                            _ => Constraint::eq(a, b),
+                        };
+                        let (holds, other) = self.branch(cond);
+                        fork = other;
+                        holds
+                    }
+                };
+                // both sides of a fork end if the store fails
+                if let Err(end) = self.store(pc, dest, Expr::Const(holds as LargeNumber)) {
+                    self.end = Some(end);
+                }
+                if let Some(other) = fork.as_mut() {
+                    if let Err(end) = other.store(pc, dest, Expr::Const(!holds as LargeNumber)) {
+                        other.end = Some(end);
+                    }
+                }
+            }
+            Opcode::RBO => {
+                let value = self.read(pc, 1, ins.pm1)?;
+                let offset = value.constant().ok_or(End::Symbolic { pc })?;
+                self.relbase = self
+                    .relbase
+                    .checked_add(offset)
+                    .ok_or_else(|| overflow(pc))?;
+            }
+        }
+        self.steps += 1;
+        if let Some(other) = fork.as_mut() {
+            other.steps = self.steps;
+        }
+        Ok(fork)
+    }
+
+    fn into_path(self, end: End) -> Path {
+        Path {
+            constraints: self.constraints,
+            outputs: self.outputs,
+            inputs: self.inputs,
+            steps: self.steps,
+            end,
+            mem: self.mem,
+        }
+    }
+}
+
+/// Every value that decodes to an instruction
+fn instruction_codes() -> impl Iterator<Item = LargeNumber> {
+    let opcodes = (1..=9).chain(Some(99));
+    opcodes
+        .flat_map(|op| (0..27).map(move |m| op + 100 * (m % 3 + 10 * (m / 3 % 3) + 100 * (m / 9))))
+}
+
+/// Explores the paths of a program
+pub struct Symbolic {
+    prog: Program,
+    inputs: Vec<LargeNumber>,
+    cells: Vec<usize>,
+    step_limit: usize,
+    path_limit: usize,
+    opaque_reads: bool,
+}
+
+impl Symbolic {
+    pub fn new(prog: Program) -> Symbolic {
+        Symbolic {
+            prog,
+            inputs: vec![],
+            cells: vec![],
+            step_limit: 100_000,
+            path_limit: 1_000,
+            opaque_reads: false,
+        }
+    }
+
+    /// Concrete values for the first inputs, later ones are variables
+    pub fn with_inputs(&mut self, inputs: Vec<LargeNumber>) -> &mut Self {
+        self.inputs = inputs;
+        self
+    }
+
+    /// Makes the initial value of a memory cell a variable
+    pub fn with_variable(&mut self, addr: usize) -> &mut Self {
+        self.cells.push(addr);
+        self
+    }
+
+    /// Steps each path may take, 100000 by default
+    pub fn with_step_limit(&mut self, limit: usize) -> &mut Self {
+        self.step_limit = limit;
+        self
+    }
+
+    /// Whether reads through symbolic addresses give `Var::Opaque` values
+    /// rather than ending the path, off by default
+    pub fn with_opaque_reads(&mut self, on: bool) -> &mut Self {
+        self.opaque_reads = on;
+        self
+    }
+
+    /// Paths explored before giving up on the rest, 1000 by default
+    pub fn with_path_limit(&mut self, limit: usize) -> &mut Self {
+        self.path_limit = limit;
+        self
+    }
+
+    /// Every feasible path, in the order they end
+    pub fn explore(&self) -> Vec<Path> {
+        let mut start = State {
+            pc: 0,
+            relbase: 0,
+            mem: self.prog.iter().map(|c| Expr::Const(*c)).collect(),
+            inputs: 0,
+            outputs: vec![],
+            constraints: vec![],
+            steps: 0,
+            end: None,
+            opaque_reads: self.opaque_reads,
+            opaque: 0,
+        };
+        for addr in &self.cells {
+            if let Err(end) = start.store(0, *addr, Expr::Var(Var::Cell(*addr))) {
+                start.end = Some(end);
+            }
+        }
+        let mut pending = vec![start];
+        let mut paths = vec![];
+        while let Some(mut state) = pending.pop() {
+            if state.end.is_none() && paths.len() + pending.len() >= self.path_limit {
+                state.end = Some(End::Abandoned { pc: state.pc });
+            }
+            let end = loop {
+                if let Some(end) = state.end.take() {
+                    break end;
+                }
+                if state.steps >= self.step_limit {
+                    break End::StepLimit { pc: state.pc };
+                }
+                pending.extend(state.step(&self.inputs));
+            };
+            paths.push(state.into_path(end));
+        }
+        paths
+    }
+}
+
+#[test]
+fn test_explore() {
+    let x = Expr::Var(Var::Input(0));
+    // outputs 1 when the input equals 8, else 0
+    let paths = Symbolic::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, 8]).explore();
+    assert_eq!(paths.len(), 2);
+    assert!(paths.iter().all(|p| p.end == End::Halted));
+    let zero = paths
+        .iter()
+        .find(|p| p.outputs == [Expr::Const(0)])
+        .unwrap();
+    assert_eq!(zero.constraints[0].to_string(), "in0 != 8");
+    assert_eq!(
+        zero.solve(&[]),
+        Check::Sat(vec![(Var::Input(0), 0)].into_iter().collect())
+    );
+    let one = paths
+        .iter()
+        .find(|p| p.outputs == [Expr::Const(1)])
+        .unwrap();
+    assert_eq!(
+        one.solve(&[]),
+        Check::Sat(vec![(Var::Input(0), 8)].into_iter().collect())
+    );
+    // forbids the only input the path allows
+    assert_eq!(
+        one.solve(&[Constraint::lt(x.clone(), Expr::Const(8))]),
+        Check::Unsat
+    );
+    // inputs past i32 keep their paths
+    let big = 1 << 40;
+    let paths = Symbolic::new(vec![3, 9, 8, 9, 10, 9, 4, 9, 99, -1, big]).explore();
+    let one = paths.iter().find(|p| p.outputs == [Expr::Const(1)]);
+    assert_eq!(
+        one.map(|p| p.solve(&[])),
+        Some(Check::Sat(vec![(Var::Input(0), big)].into_iter().collect()))
+    );
+
+    // 999 below 8, 1000 at 8 and 1001 above
+    let prog = vec![
+        3, 21, 1008, 21, 8, 20, 1005, 20, 22, 107, 8, 21, 20, 1006, 20, 31, 1106, 0, 36, 98, 0, 0,
+        1002, 21, 125, 20, 4, 20, 1105, 1, 46, 104, 999, 1105, 1, 46, 1101, 1000, 1, 20, 4, 20,
+        1105, 1, 46, 98, 99,
+    ];
+    let paths = Symbolic::new(prog).explore();
+    let mut outputs = paths
+        .iter()
+        .map(|p| match p.solve(&[]) {
+            Check::Sat(model) => (x.eval(&model).unwrap(), p.outputs[0].eval(&model).unwrap()),
+            other => panic!("{:?}", other),
+        })
+        .collect::<Vec<_>>();
+    outputs.sort();
+    // the middle path outputs 125 * in0 under in0 == 8
+    assert_eq!(outputs, vec![(0, 999), (8, 1000), (9, 1001)]);
+
+    // the output is a function of the input and a memory cell
+    let mut sym = Symbolic::new(vec![3, 9, 1, 9, 10, 9, 4, 9, 99, 0, 0]);
+    sym.with_variable(10);
+    let path = &sym.explore()[0];
+    assert_eq!(path.outputs[0].to_string(), "in0 + mem[10]");
+    let target = Constraint::eq(path.outputs[0].clone(), Expr::Const(5));
+    match path.solve(&[target, Constraint::eq(x.clone(), Expr::Const(2))]) {
+        Check::Sat(model) => assert_eq!(model[&Var::Cell(10)], 3),
+        other => panic!("{:?}", other),
+    }
+
+    // the jump target comes from the input
+    let paths = Symbolic::new(vec![3, 4, 1105, 1, -1]).explore();
+    assert_eq!(paths[0].end, End::Symbolic { pc: 2 });
+
+    // reads through the input address end the path unless they are opaque
+    let mut sym = Symbolic::new(vec![3, 3, 4, 0, 99]);
+    assert_eq!(sym.explore()[0].end, End::Symbolic { pc: 2 });
+    let path = &sym.with_opaque_reads(true).explore()[0];
+    assert_eq!(path.end, End::Halted);
+    assert_eq!(path.outputs, vec![Expr::Var(Var::Opaque(0))]);
+    assert_eq!(path.mem[3], x);
+}
+
+#[test]
+fn test_overflow() {
+    let max = LargeNumber::MAX;
+    assert_eq!(Expr::plus(Expr::Const(max), Expr::Const(1)), None);
+    let x = Expr::Var(Var::Input(0));
+    let low = Constraint::eq(x.clone(), Expr::Const(LargeNumber::MIN));
+    assert_eq!(low.to_string(), "in0 - -9223372036854775808 == 0");
+    assert_eq!(check(&[low]), Check::Unknown);
+
+    // constant sums and products, and the relative base, fail like they do
+    // on a real machine
+    let sum = vec![1101, max, 1, 5, 99, 0];
+    let product = vec![1102, max, 2, 5, 99, 0];
+    let relbase = vec![109, max, 109, 1, 99];
+    for (prog, pc) in &[(sum, 0), (product, 0), (relbase, 2)] {
+        let paths = Symbolic::new(prog.clone()).explore();
+        let end = End::Error(IntcodeError::Overflow { pc: *pc });
+        assert_eq!(paths[0].end, end);
+    }
+
+    // in0 + max + 1 only overflows for some inputs, so the constants stay
+    // apart rather than failing the path
+    let prog = vec![3, 11, 1001, 11, max, 11, 101, 1, 11, 11, 99, 0];
+    let path = &Symbolic::new(prog.clone()).explore()[0];
+    assert_eq!(path.end, End::Halted);
+    assert_eq!(path.mem[11].to_string(), format!("in0 + {} + 1", max));
+    let model = vec![(Var::Input(0), -1)].into_iter().collect();
+    assert_eq!(path.mem[11].eval(&model), Some(max));
+    let mut c = super::IntCodeComputer::with_io(prog, super::QueueIo::new(vec![-1]));
+    c.run().unwrap();
+    assert_eq!(c.mem[11], max);
+}
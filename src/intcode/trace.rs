@@ -0,0 +1,196 @@
+// Instruction-level tracing and profiling of the Intcode computer.
+//
+// Each executed instruction becomes one JSON object per line, e.g.
+//
+//     {"step":0,"pc":0,"op":"ADD","modes":"PIR","params":[9,1,2],"reads":[5,1],"write":[7,6]}
+//
+// where modes are `P`osition, `I`mmediate and `R`elative.
+//
+use super::{AddressingMode, Instruction, LargeNumber, Opcode};
+use std::collections::{BTreeMap, HashMap};
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct TraceRecord {
+    pub step: usize,
+    pub pc: usize,
+    pub ins: Instruction,
+    pub params: Vec<LargeNumber>,
+    pub reads: Vec<LargeNumber>,
+    pub write: Option<(usize, LargeNumber)>,
+}
+
+impl fmt::Display for TraceRecord {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let join = |v: &[LargeNumber]| {
+            v.iter()
+                .map(|x| x.to_string())
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+        let modes = self
+            .ins
+            .modes()
+            .iter()
+            .map(|m| match m {
+                AddressingMode::Position => 'P',
+                AddressingMode::Immediate => 'I',
+                AddressingMode::Relative => 'R',
+            })
+            .collect::<String>();
+        write!(
+            f,
+            r#"{{"step":{},"pc":{},"op":"{:?}","modes":"{}","params":[{}],"reads":[{}]"#,
+            self.step,
+            self.pc,
+            self.ins.opcode,
+            modes,
+            join(&self.params),
+            join(&self.reads)
+        )?;
+        match self.write {
+            Some((addr, value)) => write!(f, r#","write":[{},{}]}}"#, addr, value),
+            None => write!(f, r#","write":null}}"#),
+        }
+    }
+}
+
+/// Execution counts gathered while tracing
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Profile {
+    pub steps: usize,
+    pub opcodes: BTreeMap<Opcode, usize>,
+    pub addresses: HashMap<usize, usize>,
+}
+
+impl Profile {
+    fn record(&mut self, pc: usize, opcode: Opcode) {
+        self.steps += 1;
+        *self.opcodes.entry(opcode).or_insert(0) += 1;
+        *self.addresses.entry(pc).or_insert(0) += 1;
+    }
+
+    /// The `n` most executed instruction addresses, busiest first
+    pub fn hottest(&self, n: usize) -> Vec<(usize, usize)> {
+        let mut hot = self
+            .addresses
+            .iter()
+            .map(|(addr, count)| (*addr, *count))
+            .collect::<Vec<_>>();
+        hot.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+        hot.truncate(n);
+        hot
+    }
+}
+
+impl fmt::Display for Profile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "total steps: {}", self.steps)?;
+        writeln!(f, "per opcode:")?;
+        for (op, count) in &self.opcodes {
+            writeln!(f, "  {:<5} {}", format!("{:?}", op), count)?;
+        }
+        writeln!(f, "hottest addresses:")?;
+        for (addr, count) in self.hottest(10) {
+            writeln!(f, "  {:04}  {}", addr, count)?;
+        }
+        Ok(())
+    }
+}
+
+/// Opt-in recorder attached with `IntCodeComputer::with_tracer`
+#[derive(Default)]
+pub struct Tracer {
+    sink: Option<Box<dyn Write + Send>>,
+    error: Option<io::Error>,
+    current: Option<TraceRecord>,
+    pub profile: Profile,
+}
+
+impl Tracer {
+    /// Profiles without writing a trace
+    pub fn new() -> Tracer {
+        Tracer::default()
+    }
+
+    pub fn to_writer<W: Write + Send + 'static>(sink: W) -> Tracer {
+        Tracer {
+            sink: Some(Box::new(sink)),
+            ..Tracer::default()
+        }
+    }
+
+    pub fn to_file<P: AsRef<Path>>(path: P) -> io::Result<Tracer> {
+        Ok(Tracer::to_writer(BufWriter::new(File::create(path)?)))
+    }
+
+    pub(super) fn begin(&mut self, pc: usize, ins: Instruction, params: Vec<LargeNumber>) {
+        self.current = Some(TraceRecord {
+            step: self.profile.steps,
+            pc,
+            ins,
+            params,
+            reads: vec![],
+            write: None,
+        });
+    }
+
+    pub(super) fn read(&mut self, value: LargeNumber) {
+        if let Some(rec) = self.current.as_mut() {
+            rec.reads.push(value);
+        }
+    }
+
+    pub(super) fn write(&mut self, addr: usize, value: LargeNumber) {
+        if let Some(rec) = self.current.as_mut() {
+            rec.write = Some((addr, value));
+        }
+    }
+
+    /// Called once the instruction has completed
+    pub(super) fn end(&mut self) {
+        let rec = match self.current.take() {
+            Some(rec) => rec,
+            None => return,
+        };
+        self.profile.record(rec.pc, rec.ins.opcode);
+        if self.error.is_some() {
+            return;
+        }
+        if let Some(sink) = self.sink.as_mut() {
+            if let Err(e) = writeln!(sink, "{}", rec) {
+                self.error = Some(e);
+            }
+        }
+    }
+
+    /// Flushes the trace, reporting the first write error if any
+    pub fn finish(mut self) -> io::Result<Profile> {
+        if let Some(e) = self.error.take() {
+            return Err(e);
+        }
+        if let Some(sink) = self.sink.as_mut() {
+            sink.flush()?;
+        }
+        Ok(self.profile)
+    }
+}
+
+#[test]
+fn test_record_format() {
+    let rec = TraceRecord {
+        step: 0,
+        pc: 0,
+        ins: Instruction::decode(21001, 0).unwrap(),
+        params: vec![9, 1, 2],
+        reads: vec![5, 1],
+        write: Some((7, 6)),
+    };
+    assert_eq!(
+        rec.to_string(),
+        r#"{"step":0,"pc":0,"op":"ADD","modes":"PIR","params":[9,1,2],"reads":[5,1],"write":[7,6]}"#
+    );
+}
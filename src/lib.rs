@@ -0,0 +1,8 @@
+pub mod answers;
+pub mod bench;
+pub mod combinatorics;
+pub mod days;
+pub mod input;
+pub mod intcode;
+pub mod parse;
+pub mod search;
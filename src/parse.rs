@@ -0,0 +1,213 @@
+// Parsers for the puzzle input formats.
+//
+// Errors point at the offending token by line and column, both counted
+// from 1. Surrounding whitespace and trailing newlines are ignored.
+//
+use std::fmt;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct ParseError {
+    pub line: usize,
+    pub column: usize,
+    pub token: String,
+    /// What was expected instead
+    pub expected: &'static str,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.token.is_empty() {
+            write!(
+                f,
+                "line {}, column {}: expected {}",
+                self.line, self.column, self.expected
+            )
+        } else {
+            write!(
+                f,
+                "line {}, column {}: expected {}, found {:?}",
+                self.line, self.column, self.expected, self.token
+            )
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// A token with its position in the input
+#[derive(Clone, Copy, Debug, PartialEq)]
+struct Token<'a> {
+    line: usize,
+    column: usize,
+    text: &'a str,
+}
+
+impl<'a> Token<'a> {
+    fn error(&self, expected: &'static str) -> ParseError {
+        ParseError {
+            line: self.line,
+            column: self.column,
+            token: self.text.to_string(),
+            expected,
+        }
+    }
+
+    fn parse<T: FromStr>(&self, expected: &'static str) -> Result<T, ParseError> {
+        self.text.parse().map_err(|_| self.error(expected))
+    }
+}
+
+/// Non-blank lines, trimmed
+fn lines(s: &str) -> impl Iterator<Item = Token<'_>> {
+    s.lines()
+        .enumerate()
+        .filter(|(_, l)| !l.trim().is_empty())
+        .map(|(i, l)| trimmed(i + 1, 1, l))
+}
+
+fn trimmed(line: usize, column: usize, text: &str) -> Token<'_> {
+    let lead = text.len() - text.trim_start().len();
+    Token {
+        line,
+        column: column + text[..lead].chars().count(),
+        text: text.trim(),
+    }
+}
+
+/// Splits a line on `sep`, trimming each field
+fn fields(line: Token<'_>, sep: char) -> impl Iterator<Item = Token<'_>> {
+    let mut column = line.column;
+    line.text.split(sep).map(move |field| {
+        let token = trimmed(line.line, column, field);
+        column += field.chars().count() + 1;
+        token
+    })
+}
+
+/// Integers separated by commas, possibly over several lines
+pub fn ints<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> {
+    lines(s)
+        .flat_map(|l| fields(l, ','))
+        .map(|t| t.parse("an integer"))
+        .collect()
+}
+
+/// One value per line
+pub fn per_line<T: FromStr>(s: &str) -> Result<Vec<T>, ParseError> {
+    lines(s).map(|t| t.parse("a number")).collect()
+}
+
+/// A string of decimal digits
+pub fn digits(s: &str) -> Result<Vec<u8>, ParseError> {
+    let line = lines(s).next().ok_or(ParseError {
+        line: 1,
+        column: 1,
+        token: String::new(),
+        expected: "digits",
+    })?;
+    line.text
+        .chars()
+        .enumerate()
+        .map(|(i, c)| {
+            c.to_digit(10).map(|d| d as u8).ok_or(ParseError {
+                line: line.line,
+                column: line.column + i,
+                token: c.to_string(),
+                expected: "a digit",
+            })
+        })
+        .collect()
+}
+
+/// `A)B` pairs, one per line
+pub fn edges(s: &str) -> Result<Vec<(&str, &str)>, ParseError> {
+    lines(s)
+        .map(|l| {
+            let mut parts = fields(l, ')');
+            match (parts.next(), parts.next(), parts.next()) {
+                (Some(a), Some(b), None) if !a.text.is_empty() && !b.text.is_empty() => {
+                    Ok((a.text, b.text))
+                }
+                _ => Err(l.error("an edge like A)B")),
+            }
+        })
+        .collect()
+}
+
+/// One line of comma separated moves like `R75`, `U7`
+pub type Path = Vec<(char, usize)>;
+
+/// A path of moves per line
+pub fn directions(s: &str) -> Result<Vec<Path>, ParseError> {
+    lines(s)
+        .map(|l| {
+            fields(l, ',')
+                .map(|t| {
+                    let mut chars = t.text.chars();
+                    match chars.next() {
+                        Some(d @ ('U' | 'D' | 'L' | 'R')) => Ok((
+                            d,
+                            Token {
+                                column: t.column + 1,
+                                text: chars.as_str(),
+                                ..t
+                            }
+                            .parse("a step count")?,
+                        )),
+                        _ => Err(t.error("a move like R75")),
+                    }
+                })
+                .collect()
+        })
+        .collect()
+}
+
+/// An inclusive range like `109165-576723`
+pub fn range<T: FromStr>(s: &str) -> Result<RangeInclusive<T>, ParseError> {
+    let line = lines(s).next().ok_or(ParseError {
+        line: 1,
+        column: 1,
+        token: String::new(),
+        expected: "a range like 10-20",
+    })?;
+    let mut parts = fields(line, '-');
+    match (parts.next(), parts.next(), parts.next()) {
+        (Some(lo), Some(hi), None) => Ok(lo.parse("a number")?..=hi.parse("a number")?),
+        _ => Err(line.error("a range like 10-20")),
+    }
+}
+
+#[test]
+fn test_ints() {
+    assert_eq!(ints::<i64>("1,-2, 3\n"), Ok(vec![1, -2, 3]));
+    assert_eq!(ints::<i64>("1,2\n3,4\n\n"), Ok(vec![1, 2, 3, 4]));
+    let err = ints::<i64>("1,2\n3, x4,5").unwrap_err();
+    assert_eq!((err.line, err.column, err.token.as_str()), (2, 4, "x4"));
+    assert_eq!(
+        err.to_string(),
+        "line 2, column 4: expected an integer, found \"x4\""
+    );
+    assert_eq!(ints::<i64>("1,,2").unwrap_err().column, 3);
+    assert_eq!(per_line::<i32>(" 12\n14\n"), Ok(vec![12, 14]));
+}
+
+#[test]
+fn test_formats() {
+    assert_eq!(digits("0122\n"), Ok(vec![0, 1, 2, 2]));
+    assert_eq!(digits("01a2").unwrap_err().column, 3);
+    assert_eq!(edges("COM)B\nB)C\n"), Ok(vec![("COM", "B"), ("B", "C")]));
+    let err = edges("COM)B\nBC\n").unwrap_err();
+    assert_eq!((err.line, err.token.as_str()), (2, "BC"));
+    assert_eq!(
+        directions("R8,U5\nU7,L6\n"),
+        Ok(vec![vec![('R', 8), ('U', 5)], vec![('U', 7), ('L', 6)]])
+    );
+    let err = directions("R8,X5").unwrap_err();
+    assert_eq!((err.column, err.token.as_str()), (4, "X5"));
+    let err = directions("R8,U5x").unwrap_err();
+    assert_eq!((err.column, err.token.as_str()), (5, "5x"));
+    assert_eq!(range::<i32>("109165-576723\n"), Ok(109165..=576723));
+    assert!(range::<i32>("12").is_err());
+}
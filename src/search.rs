@@ -0,0 +1,116 @@
+// Parallel search for the best of a set of candidates.
+//
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Mutex;
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Objective {
+    Min,
+    Max,
+}
+
+impl Objective {
+    /// Whether `a` beats `b`
+    pub fn better<V: Ord>(self, a: &V, b: &V) -> bool {
+        match self {
+            Objective::Min => a < b,
+            Objective::Max => a > b,
+        }
+    }
+}
+
+/// Default size of the worker pool
+pub fn workers() -> usize {
+    std::thread::available_parallelism().map_or(1, |n| n.get())
+}
+
+/// Scores every candidate on a pool of `workers` threads and returns the
+/// best one with its score, the earliest candidate on ties
+///
+/// Candidates are pulled from the iterator as workers free up. The first
+/// error stops the search.
+pub fn best<I, C, V, E, F>(
+    candidates: I,
+    objective: Objective,
+    workers: usize,
+    score: F,
+) -> Result<Option<(C, V)>, E>
+where
+    I: IntoIterator<Item = C>,
+    I::IntoIter: Send,
+    C: Send,
+    V: Ord + Send,
+    E: Send,
+    F: Fn(&C) -> Result<V, E> + Sync,
+{
+    let candidates = Mutex::new(candidates.into_iter().enumerate());
+    let failed = AtomicBool::new(false);
+    let results = std::thread::scope(|s| {
+        let handles = (0..workers.max(1))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut best: Option<(usize, C, V)> = None;
+                    while !failed.load(Ordering::Relaxed) {
+                        let next = candidates.lock().unwrap().next();
+                        let (i, candidate) = match next {
+                            Some(next) => next,
+                            None => break,
+                        };
+                        let value = score(&candidate).map_err(|e| {
+                            failed.store(true, Ordering::Relaxed);
+                            e
+                        })?;
+                        // indices only grow within a worker, so ties keep the first
+                        if best
+                            .as_ref()
+                            .map_or(true, |(_, _, b)| objective.better(&value, b))
+                        {
+                            best = Some((i, candidate, value));
+                        }
+                    }
+                    Ok(best)
+                })
+            })
+            .collect::<Vec<_>>();
+        handles
+            .into_iter()
+            .map(|h| h.join().unwrap())
+            .collect::<Result<Vec<_>, E>>()
+    })?;
+
+    let best = results
+        .into_iter()
+        .flatten()
+        .fold(None, |best, (i, c, value)| match best {
+            Some((j, b, bv)) if objective.better(&bv, &value) || (bv == value && j < i) => {
+                Some((j, b, bv))
+            }
+            _ => Some((i, c, value)),
+        });
+    Ok(best.map(|(_, candidate, value)| (candidate, value)))
+}
+
+#[test]
+fn test_best() {
+    let candidates = 0..100;
+    let score = |x: &i64| Ok::<_, ()>((x - 42).abs() % 10);
+    for workers in 1..=4 {
+        assert_eq!(
+            best(candidates.clone(), Objective::Min, workers, score),
+            Ok(Some((2, 0)))
+        );
+        assert_eq!(
+            best(candidates.clone(), Objective::Max, workers, score),
+            Ok(Some((3, 9)))
+        );
+    }
+    assert_eq!(best(0..0, Objective::Max, 4, score), Ok(None));
+    assert_eq!(
+        best(candidates, Objective::Max, 4, |x: &i64| if *x == 50 {
+            Err("bad candidate")
+        } else {
+            Ok(*x)
+        }),
+        Err("bad candidate")
+    );
+}